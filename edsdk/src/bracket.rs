@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! Exposure bracketing, either run by the camera from its bracketing settings
+//! or driven from the host by stepping an exposure setting between captures.
+//!
+//! The layout of `WhiteBalanceBracket` depends on the camera model: it is
+//! read and written as raw integers with [`props::WhiteBalanceBracket`].
+
+use crate::*;
+
+/// Bracketing setting of the camera, as laid out by the `AEBracket`,
+/// `FEBracket` and `ISOBracket` properties: the step, then the number of frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BracketSetting {
+    /// Step between two frames, in stops. Bracketing is off when zero.
+    pub step: EdsRational,
+    /// Number of frames, `None` on cameras only reporting the step.
+    pub count: Option<u32>,
+}
+
+impl BracketSetting {
+    /// Setting of `count` frames `steps` increments apart.
+    pub fn new(steps: u32, increment: StopIncrement, count: u32) -> Self {
+        let denominator = match increment {
+            StopIncrement::Third => 3,
+            StopIncrement::Half => 2,
+            StopIncrement::Full => 1,
+        };
+        Self {
+            step: EdsRational {
+                numerator: steps as EdsInt32,
+                denominator,
+            },
+            count: Some(count),
+        }
+    }
+
+    /// Step between two frames, in stops.
+    pub fn stops(&self) -> f64 {
+        match self.step.denominator {
+            0 => 0.0,
+            denominator => f64::from(self.step.numerator) / f64::from(denominator),
+        }
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.step.numerator == 0
+    }
+}
+
+impl PropertyType for BracketSetting {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        match Vec::<EdsRational>::from_value(value)?[..] {
+            [step] => Ok(Self { step, count: None }),
+            [step, count, ..] => Ok(Self {
+                step,
+                count: count
+                    .numerator
+                    .checked_div(count.denominator as EdsInt32)
+                    .map(|count| count.max(0) as u32),
+            }),
+            [] => Err(EdsError::PropertiesMismatch.into()),
+        }
+    }
+
+    fn into_value(self) -> PropertyValue {
+        let mut values = vec![self.step];
+        if let Some(count) = self.count {
+            values.push(EdsRational {
+                numerator: count as EdsInt32,
+                denominator: 1,
+            });
+        }
+        PropertyValue::RationalArray(values)
+    }
+}
+
+/// Exposure setting a host-driven bracket can step, see [`CameraSession::bracket`].
+pub trait BracketProperty: Property<Value: Stops> {
+    /// `1.0` if a higher position, see [`Stops::stops`], brightens the image,
+    /// `-1.0` if it darkens it.
+    const EXPOSURE: f64;
+}
+
+impl BracketProperty for props::Tv {
+    const EXPOSURE: f64 = 1.0;
+}
+
+impl BracketProperty for props::Av {
+    const EXPOSURE: f64 = -1.0;
+}
+
+impl BracketProperty for props::ISOSpeed {
+    const EXPOSURE: f64 = 1.0;
+}
+
+/// Frame of a host-driven bracket.
+#[derive(Debug, Clone)]
+pub struct BracketFrame<T> {
+    /// Offset asked for, in EV.
+    pub offset: f64,
+    /// Value closest to the offset among those allowed by the camera.
+    pub value: T,
+    pub items: Vec<CapturedItem>,
+}
+
+/// Frames of a host-driven bracket, in the order of the offsets.
+#[derive(Debug, Clone)]
+pub struct Bracket<T> {
+    /// Value of the setting before the bracket, restored after it.
+    pub base: T,
+    pub frames: Vec<BracketFrame<T>>,
+}
+
+impl CameraSession {
+    /// Bracketing modes enabled on the camera.
+    pub async fn get_bracket_modes(&self) -> Result<Vec<EdsBracket>, Error> {
+        let modes = self.get::<props::Bracket>().await?;
+        if modes == u32::from(EdsBracket::Unknown) {
+            return Ok(Vec::new());
+        }
+        Ok([
+            EdsBracket::AEB,
+            EdsBracket::ISOB,
+            EdsBracket::WBB,
+            EdsBracket::FEB,
+        ]
+        .into_iter()
+        .filter(|mode| modes & u32::from(*mode) != 0)
+        .collect())
+    }
+
+    pub async fn get_ae_bracket(&self) -> Result<BracketSetting, Error> {
+        self.get::<props::AEBracket>().await
+    }
+
+    /// Sets the auto exposure bracketing. Most cameras only allow reading it.
+    pub async fn set_ae_bracket(&self, setting: BracketSetting) -> Result<(), Error> {
+        self.set::<props::AEBracket>(setting).await
+    }
+
+    pub async fn get_fe_bracket(&self) -> Result<BracketSetting, Error> {
+        self.get::<props::FEBracket>().await
+    }
+
+    /// Sets the flash exposure bracketing. Most cameras only allow reading it.
+    pub async fn set_fe_bracket(&self, setting: BracketSetting) -> Result<(), Error> {
+        self.set::<props::FEBracket>(setting).await
+    }
+
+    pub async fn get_iso_bracket(&self) -> Result<BracketSetting, Error> {
+        self.get::<props::ISOBracket>().await
+    }
+
+    /// Sets the ISO bracketing. Most cameras only allow reading it.
+    pub async fn set_iso_bracket(&self, setting: BracketSetting) -> Result<(), Error> {
+        self.set::<props::ISOBracket>(setting).await
+    }
+
+    /// Captures one frame per offset, in EV, by stepping the setting `P`. See
+    /// [`CameraSession::bracket_with`].
+    pub async fn bracket<P: BracketProperty>(
+        &self,
+        offsets: &[f64],
+    ) -> Result<Bracket<P::Value>, Error> {
+        self.bracket_with::<P>(offsets, CaptureConfig::default())
+            .await
+    }
+
+    /// Captures one frame per offset, in EV, by setting `P` to the value the
+    /// closest to the offset among those of its descriptor, e.g.
+    /// `bracket_with::<props::Tv>(&[-2.0, 0.0, 2.0], config)` for an HDR
+    /// sequence. Captures are made as by [`CameraSession::capture_with`].
+    ///
+    /// The original value of `P` is restored afterwards, whether the bracket
+    /// succeeded or not, but not if the returned future is dropped. Fails with
+    /// `EdsError::NotSupported` before the first frame if the current value has
+    /// no position, such as [`EdsISO::Auto`], or the camera allows no value.
+    pub async fn bracket_with<P: BracketProperty>(
+        &self,
+        offsets: &[f64],
+        config: CaptureConfig,
+    ) -> Result<Bracket<P::Value>, Error> {
+        let base = self.get::<P>().await?;
+        let allowed = self.describe::<P>().await?.values;
+        let values = offsets
+            .iter()
+            .map(|offset| base.shift(offset * P::EXPOSURE, &allowed))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::new(EdsError::NotSupported))?;
+        let shot = self.shoot_bracket::<P>(offsets, values, config).await;
+        let restored = self.set::<P>(base).await;
+        let frames = shot?;
+        restored?;
+        Ok(Bracket { base, frames })
+    }
+
+    async fn shoot_bracket<P: BracketProperty>(
+        &self,
+        offsets: &[f64],
+        values: Vec<P::Value>,
+        config: CaptureConfig,
+    ) -> Result<Vec<BracketFrame<P::Value>>, Error> {
+        let mut frames = Vec::with_capacity(values.len());
+        for (&offset, value) in offsets.iter().zip(values) {
+            self.set::<P>(value).await?;
+            let items = self.capture_with(config).await?;
+            frames.push(BracketFrame {
+                offset,
+                value,
+                items,
+            });
+        }
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rational(numerator: EdsInt32, denominator: EdsUInt32) -> EdsRational {
+        EdsRational {
+            numerator,
+            denominator,
+        }
+    }
+
+    #[test]
+    fn setting_round_trips() {
+        let setting = BracketSetting::new(2, StopIncrement::Third, 5);
+        let value = setting.into_value();
+        assert_eq!(
+            value,
+            PropertyValue::RationalArray(vec![rational(2, 3), rational(5, 1)])
+        );
+        assert_eq!(BracketSetting::from_value(value).unwrap(), setting);
+    }
+
+    #[test]
+    fn step_only() {
+        let setting = BracketSetting {
+            step: rational(1, 2),
+            count: None,
+        };
+        let value = setting.into_value();
+        assert_eq!(value, PropertyValue::RationalArray(vec![rational(1, 2)]));
+        assert_eq!(BracketSetting::from_value(value).unwrap(), setting);
+    }
+
+    #[test]
+    fn no_count_code() {
+        let value = PropertyValue::RationalArray(vec![rational(1, 3), rational(0, 0)]);
+        let setting = BracketSetting::from_value(value).unwrap();
+        assert_eq!(setting.step, rational(1, 3));
+        assert_eq!(setting.count, None);
+    }
+
+    #[test]
+    fn count_is_divided_and_clamped() {
+        let value = PropertyValue::RationalArray(vec![rational(0, 1), rational(6, 2)]);
+        assert_eq!(BracketSetting::from_value(value).unwrap().count, Some(3));
+        let value = PropertyValue::RationalArray(vec![rational(0, 1), rational(-3, 1)]);
+        let setting = BracketSetting::from_value(value).unwrap();
+        assert_eq!(setting.count, Some(0));
+        assert!(setting.is_off());
+    }
+
+    #[test]
+    fn empty_value_is_rejected() {
+        let value = PropertyValue::RationalArray(vec![]);
+        assert_eq!(
+            BracketSetting::from_value(value).unwrap_err().code(),
+            EdsError::PropertiesMismatch
+        );
+        let value = PropertyValue::UInt32(3);
+        assert!(BracketSetting::from_value(value).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::{capture::capture_error, *};
+use std::{future::Future, pin::pin, time::Duration};
+use tokio::{
+    sync::broadcast::error::RecvError,
+    time::{self, Instant},
+};
+
+/// Bulb exposure in progress, ended when dropped if [`BulbGuard::end`] was not
+/// called, so that a cancelled exposure does not leave the shutter open.
+struct BulbGuard<'a> {
+    session: &'a CameraSession,
+    open: bool,
+}
+
+impl<'a> BulbGuard<'a> {
+    /// Locks the UI of the camera and opens the shutter.
+    async fn start(session: &'a CameraSession) -> Result<Self, Error> {
+        session
+            .send_status_command(EdsCameraStatusCommand::UILock, 0)
+            .await?;
+        if let Err(err) = session.send_command(EdsCameraCommand::BulbStart, 0).await {
+            let _ = session
+                .send_status_command(EdsCameraStatusCommand::UIUnLock, 0)
+                .await;
+            return Err(err);
+        }
+        Ok(Self {
+            session,
+            open: true,
+        })
+    }
+
+    /// Closes the shutter and unlocks the UI of the camera.
+    async fn end(mut self) -> Result<(), Error> {
+        self.open = false;
+        let ended = self
+            .session
+            .send_command(EdsCameraCommand::BulbEnd, 0)
+            .await;
+        let unlocked = self
+            .session
+            .send_status_command(EdsCameraStatusCommand::UIUnLock, 0)
+            .await;
+        ended.and(unlocked)
+    }
+}
+
+impl Drop for BulbGuard<'_> {
+    fn drop(&mut self) {
+        if self.open {
+            let camera = self.session.as_raw();
+            let _ = check_call!(EdsSendCommand(camera, EdsCameraCommand::BulbEnd, 0));
+            let _ = check_call!(EdsSendStatusCommand(
+                camera,
+                EdsCameraStatusCommand::UIUnLock,
+                0
+            ));
+        }
+    }
+}
+
+impl CameraSession {
+    /// Sets [`EdsTv::TvBulb`] unless already set. Fails with
+    /// `EdsError::NotSupported` if the camera does not allow it, e.g. when the
+    /// mode dial is not on M or B.
+    async fn ensure_bulb(&self) -> Result<(), Error> {
+        if self.get_tv().await? == EdsTv::TvBulb {
+            return Ok(());
+        }
+        if !self.get_all_tv().await?.contains(&EdsTv::TvBulb) {
+            return Err(EdsError::NotSupported.into());
+        }
+        self.set_tv(EdsTv::TvBulb).await
+    }
+
+    /// Exposes for `duration` in bulb mode and downloads the resulting files.
+    /// See [`CameraSession::bulb_exposure_with`].
+    pub async fn bulb_exposure(&self, duration: Duration) -> Result<Vec<CapturedItem>, Error> {
+        self.bulb_exposure_with(duration, std::future::pending(), |_| ())
+            .await
+    }
+
+    /// Exposes in bulb mode until `duration` has elapsed or `stop` resolves,
+    /// whichever comes first, then downloads the resulting files as
+    /// [`CameraSession::capture`] does.
+    ///
+    /// Switches Tv to bulb if needed. `progress` is called with the exposure
+    /// time reported by the camera, about every second. Dropping the returned
+    /// future ends the exposure, without downloading the image.
+    pub async fn bulb_exposure_with<S, P>(
+        &self,
+        duration: Duration,
+        stop: S,
+        mut progress: P,
+    ) -> Result<Vec<CapturedItem>, Error>
+    where
+        S: Future<Output = ()>,
+        P: FnMut(Duration),
+    {
+        self.ensure_bulb().await?;
+        let expected = self.expected_items().await?;
+        let mut events = self.events();
+        let bulb = BulbGuard::start(self).await?;
+        let timer = time::sleep(duration);
+        let mut timer = pin!(timer);
+        let mut stop = pin!(stop);
+        loop {
+            tokio::select! {
+                _ = &mut timer => break,
+                _ = &mut stop => break,
+                event = events.recv() => match event {
+                    Ok(CameraEvent::State {
+                        event: EdsStateEvent::BulbExposureTime,
+                        data,
+                    }) => progress(Duration::from_secs(data.into())),
+                    Ok(CameraEvent::State {
+                        event: EdsStateEvent::CaptureError,
+                        data,
+                    }) => return Err(capture_error(data)),
+                    Ok(_) | Err(RecvError::Lagged(_)) => (),
+                    Err(RecvError::Closed) => return Err(EdsError::SessionNotOpen.into()),
+                },
+            }
+        }
+        bulb.end().await?;
+        // Long exposure noise reduction may take as long as the exposure itself.
+        let deadline = Instant::now() + duration + CaptureConfig::default().timeout;
+        // Subscribed before the start, so that no transfer request is missed.
+        self.receive_items(events, expected, deadline).await
+    }
+}
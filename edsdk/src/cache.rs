@@ -0,0 +1,325 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::*;
+use std::{any::Any, collections::HashMap, sync::Once};
+use tokio::sync::watch;
+
+/// Properties read on the first use of the cache, the others being cached from
+/// their first event or [`CameraSession::watch`].
+const PRELOADED: &[EdsPropertyID] = &[
+    EdsPropertyID::AEMode,
+    EdsPropertyID::DriveMode,
+    EdsPropertyID::ISOSpeed,
+    EdsPropertyID::Av,
+    EdsPropertyID::Tv,
+    EdsPropertyID::ExposureCompensation,
+    EdsPropertyID::MeteringMode,
+    EdsPropertyID::AFMode,
+    EdsPropertyID::WhiteBalance,
+    EdsPropertyID::ColorTemperature,
+    EdsPropertyID::PictureStyle,
+    EdsPropertyID::ImageQuality,
+    EdsPropertyID::SaveTo,
+    EdsPropertyID::BatteryLevel,
+    EdsPropertyID::AvailableShots,
+    EdsPropertyID::EvfMode,
+    EdsPropertyID::EvfOutputDevice,
+];
+
+/// Senders of the typed values of a property, see [`Typed`].
+trait Watchers: Send {
+    fn value_changed(&self, value: &PropertyValue);
+    fn desc_changed(&self, desc: &PropertyDescriptor<EdsInt32>);
+    fn as_any(&mut self) -> &mut dyn Any;
+}
+
+/// Senders of [`CameraSession::watch`] and [`CameraSession::watch_desc`] for a
+/// property of type `T`, created on the first call of each.
+struct Typed<T> {
+    value: Option<watch::Sender<T>>,
+    desc: Option<watch::Sender<PropertyDescriptor<T>>>,
+}
+
+impl<T> Default for Typed<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            desc: None,
+        }
+    }
+}
+
+impl<T> Watchers for Typed<T>
+where
+    T: PropertyType + Send + Sync + 'static,
+{
+    fn value_changed(&self, value: &PropertyValue) {
+        if let Some(sender) = &self.value
+            && let Ok(value) = T::from_value(value.clone())
+        {
+            sender.send_replace(value);
+        }
+    }
+
+    fn desc_changed(&self, desc: &PropertyDescriptor<EdsInt32>) {
+        if let Some(sender) = &self.desc {
+            sender.send_replace(desc.decode());
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Orders the reads of a value made outside of the lock of the cache, so that
+/// an older read finishing last does not replace a newer one.
+#[derive(Debug, Default)]
+struct Sequence {
+    started: u64,
+    stored: u64,
+}
+
+impl Sequence {
+    /// Number of a read about to start.
+    fn start(&mut self) -> u64 {
+        self.started += 1;
+        self.started
+    }
+
+    /// Whether the result of the read `seq` is to be stored, i.e. no later
+    /// read has been stored yet.
+    fn store(&mut self, seq: u64) -> bool {
+        if seq <= self.stored {
+            return false;
+        }
+        self.stored = seq;
+        true
+    }
+}
+
+#[derive(Default)]
+struct Entry {
+    value: Option<PropertyValue>,
+    desc: Option<PropertyDescriptor<EdsInt32>>,
+    value_reads: Sequence,
+    desc_reads: Sequence,
+    watchers: Option<Box<dyn Watchers>>,
+}
+
+impl Entry {
+    fn typed<T>(&mut self) -> Result<&mut Typed<T>, Error>
+    where
+        T: PropertyType + Send + Sync + 'static,
+    {
+        self.watchers
+            .get_or_insert_with(|| Box::new(Typed::<T>::default()))
+            .as_any()
+            .downcast_mut()
+            .ok_or(Error::new(EdsError::PropertiesMismatch))
+    }
+}
+
+/// Last values and descriptors read from the properties of a session, refreshed
+/// on the property events.
+pub(crate) struct PropertyCache {
+    camera: Camera,
+    entries: std::sync::Mutex<HashMap<EdsPropertyID, Entry>>,
+    preloaded: Once,
+}
+
+impl std::fmt::Debug for PropertyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PropertyCache")
+            .field("cached", &self.entries().len())
+            .finish()
+    }
+}
+
+impl PropertyCache {
+    pub(crate) fn new(camera: &Camera) -> Self {
+        Self {
+            camera: camera.clone(),
+            entries: Default::default(),
+            preloaded: Once::new(),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<EdsPropertyID, Entry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Whether the cache has been used, and so has to be kept up to date.
+    pub(crate) fn is_used(&self) -> bool {
+        self.preloaded.is_completed()
+    }
+
+    /// Reads the properties most applications display, on the first call only.
+    /// Those the camera does not have are skipped.
+    fn preload(&self) {
+        self.preloaded.call_once(|| {
+            for &id in PRELOADED {
+                let _ = self.refresh_value(id);
+                let _ = self.refresh_desc(id);
+            }
+        });
+    }
+
+    /// Re-reads the value or the descriptor of a property on its event.
+    pub(crate) fn on_event(&self, event: EdsPropertyEvent, id: EdsPropertyID) {
+        if let EdsPropertyID::Unknown(_) = id {
+            return;
+        }
+        match event {
+            EdsPropertyEvent::PropertyChanged => {
+                let _ = self.refresh_value(id);
+            }
+            EdsPropertyEvent::PropertyDescChanged => {
+                let _ = self.refresh_desc(id);
+            }
+            _ => (),
+        }
+    }
+
+    fn refresh_value(&self, id: EdsPropertyID) -> Result<(), Error> {
+        let seq = self.entries().entry(id).or_default().value_reads.start();
+        let value = read_property(self.camera.as_base_ref(), id, 0);
+        let mut entries = self.entries();
+        let entry = entries.entry(id).or_default();
+        if !entry.value_reads.store(seq) {
+            return value.map(|_| ());
+        }
+        if let (Ok(value), Some(watchers)) = (&value, &entry.watchers) {
+            watchers.value_changed(value);
+        }
+        entry.value = value.as_ref().ok().cloned();
+        value.map(|_| ())
+    }
+
+    fn refresh_desc(&self, id: EdsPropertyID) -> Result<(), Error> {
+        let seq = self.entries().entry(id).or_default().desc_reads.start();
+        let desc = read_descriptor(self.camera.as_base_ref(), id);
+        let mut entries = self.entries();
+        let entry = entries.entry(id).or_default();
+        if !entry.desc_reads.store(seq) {
+            return desc.map(|_| ());
+        }
+        if let (Ok(desc), Some(watchers)) = (&desc, &entry.watchers) {
+            watchers.desc_changed(desc);
+        }
+        entry.desc = desc.as_ref().ok().cloned();
+        desc.map(|_| ())
+    }
+
+    fn cached_value(&self, id: EdsPropertyID) -> Option<PropertyValue> {
+        self.preload();
+        self.entries().get(&id)?.value.clone()
+    }
+
+    fn watch<T>(&self, id: EdsPropertyID) -> Result<watch::Receiver<T>, Error>
+    where
+        T: PropertyType + Send + Sync + 'static,
+    {
+        if self.cached_value(id).is_none() {
+            self.refresh_value(id)?;
+        }
+        let mut entries = self.entries();
+        let entry = entries.entry(id).or_default();
+        // Taken under the lock, so that no event is missed before the sender
+        // is registered.
+        let value = entry.value.clone();
+        let typed = entry.typed::<T>()?;
+        if let Some(sender) = &typed.value {
+            return Ok(sender.subscribe());
+        }
+        let value = T::from_value(value.ok_or(Error::new(EdsError::PropertiesUnavailable))?)?;
+        let (sender, receiver) = watch::channel(value);
+        typed.value = Some(sender);
+        Ok(receiver)
+    }
+
+    fn watch_desc<T>(
+        &self,
+        id: EdsPropertyID,
+    ) -> Result<watch::Receiver<PropertyDescriptor<T>>, Error>
+    where
+        T: PropertyType + Send + Sync + 'static,
+    {
+        self.preload();
+        let cached = self
+            .entries()
+            .get(&id)
+            .is_some_and(|entry| entry.desc.is_some());
+        if !cached {
+            self.refresh_desc(id)?;
+        }
+        let mut entries = self.entries();
+        let entry = entries.entry(id).or_default();
+        let desc = entry.desc.as_ref().map(|desc| desc.decode());
+        let typed = entry.typed::<T>()?;
+        if let Some(sender) = &typed.desc {
+            return Ok(sender.subscribe());
+        }
+        let desc = desc.ok_or(Error::new(EdsError::PropertiesUnavailable))?;
+        let (sender, receiver) = watch::channel(desc);
+        typed.desc = Some(sender);
+        Ok(receiver)
+    }
+}
+
+impl CameraSession {
+    /// Subscribes to the value of the property `P`, re-read whenever the camera
+    /// reports it changed, e.g. when a dial of the body is turned.
+    ///
+    /// Updates are only received while `eds_get_event` is being called, see
+    /// [`Sdk::with_event_pump`].
+    pub async fn watch<P>(&self) -> Result<watch::Receiver<P::Value>, Error>
+    where
+        P: Property,
+        P::Value: Send + Sync + 'static,
+    {
+        self.events.cache.watch(P::ID)
+    }
+
+    /// Subscribes to the descriptor of the property `P`, re-read whenever the
+    /// camera reports it changed. See [`CameraSession::watch`].
+    pub async fn watch_desc<P>(
+        &self,
+    ) -> Result<watch::Receiver<PropertyDescriptor<P::Value>>, Error>
+    where
+        P: Property,
+        P::Value: Send + Sync + 'static,
+    {
+        self.events.cache.watch_desc(P::ID)
+    }
+
+    /// Last value of the property `P` read by the cache, without querying the
+    /// camera. `None` if it has not been read, or could not be decoded as `P::Value`.
+    pub fn cached<P: Property>(&self) -> Option<P::Value> {
+        P::Value::from_value(self.events.cache.cached_value(P::ID)?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn older_read_is_not_stored() {
+        let mut reads = Sequence::default();
+        let older = reads.start();
+        let newer = reads.start();
+        assert!(reads.store(newer));
+        assert!(!reads.store(older));
+    }
+
+    #[test]
+    fn reads_are_stored_in_order() {
+        let mut reads = Sequence::default();
+        let first = reads.start();
+        assert!(reads.store(first));
+        let second = reads.start();
+        assert!(reads.store(second));
+    }
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::*;
+use std::{collections::HashSet, time::Duration};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    time::{self, Instant},
+};
+
+/// File transferred from the camera after a shot.
+#[derive(Debug, Clone)]
+pub struct CapturedItem {
+    /// Information of the item on the camera. The images of a RAW+JPEG shot
+    /// share the same `group_id`.
+    pub info: EdsDirectoryItemInfo,
+    pub data: Vec<u8>,
+}
+
+impl CapturedItem {
+    pub fn file_name(&self) -> String {
+        self.info.get_sz_file_name()
+    }
+}
+
+/// Configuration of [`CameraSession::capture_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureConfig {
+    /// Delay after which the shot is considered lost, from the shutter release
+    /// to the end of the last download.
+    pub timeout: Duration,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Error reported by a `CaptureError` state event, whose data is the error code.
+pub(crate) fn capture_error(data: EdsUInt32) -> Error {
+    match Error::from_code(data) {
+        err if err.code() == EdsError::Ok => EdsError::InternalError.into(),
+        err => err,
+    }
+}
+
+/// Downloads `item` to memory, then tells the camera it can release it.
+async fn download_item(
+    item: &DirectoryItem,
+    info: EdsDirectoryItemInfo,
+) -> Result<CapturedItem, Error> {
+    let downloaded = async {
+        let stream = eds_create_memory_stream(info.size)?;
+        eds_download(item, info.size, &stream).await?;
+        let data = data_from_out_stream(&stream).await?;
+        Ok(CapturedItem { info, data })
+    }
+    .await;
+    match downloaded {
+        Ok(captured) => eds_download_complete(item).await.map(|()| captured),
+        Err(err) => {
+            let _ = eds_download_cancel(item).await;
+            Err(err)
+        }
+    }
+}
+
+impl CameraSession {
+    /// Takes a picture and downloads the files it produced. See
+    /// [`CameraSession::capture_with`].
+    pub async fn capture(&self) -> Result<Vec<CapturedItem>, Error> {
+        self.capture_with(CaptureConfig::default()).await
+    }
+
+    /// Takes a picture and downloads the files it produced, two for RAW+JPEG
+    /// qualities, in the order the camera sent them.
+    ///
+    /// Requires [`EdsSaveTo::Host`] or [`EdsSaveTo::Both`], the files being
+    /// downloaded from the `DirItemRequestTransfer` events, and so an event
+    /// pump (see [`Sdk::with_event_pump`]). Fails with the `TakePicture*Ng` code
+    /// if the camera refuses to shoot (see [`Error::is_take_picture_failure`]),
+    /// and with `EdsError::WaitTimeoutError` after `config.timeout`.
+    ///
+    /// The files of the shot are told apart from others by their group, see
+    /// `EdsDirectoryItemInfo::group_id`: files of other groups are cancelled.
+    /// When a shot times out or fails before all its files are received, its
+    /// group is remembered so that its late files are cancelled rather than
+    /// returned by a later capture; late files of a shot that sent none cannot
+    /// be recognized. No other capture should run on the session at the same time.
+    pub async fn capture_with(&self, config: CaptureConfig) -> Result<Vec<CapturedItem>, Error> {
+        let expected = self.expected_items().await?;
+        let events = self.events();
+        let deadline = Instant::now() + config.timeout;
+        self.release_shutter().await?;
+        self.receive_items(events, expected, deadline).await
+    }
+
+    /// Number of files produced by a shot, checking that they are sent to the host.
+    pub(crate) async fn expected_items(&self) -> Result<usize, Error> {
+        if let EdsSaveTo::Camera = self.get::<props::SaveTo>().await? {
+            return Err(EdsError::InvalidFnCall.into());
+        }
+        let quality = self.get_property(EdsPropertyID::ImageQuality).await?;
+        Ok(image_count(EdsUInt32::from_value(quality)?))
+    }
+
+    /// Presses the shutter button completely, then releases it.
+    async fn release_shutter(&self) -> Result<(), Error> {
+        let pressed = self
+            .send_command(
+                EdsCameraCommand::PressShutterButton,
+                EdsShutterButton::Completely,
+            )
+            .await;
+        let released = self
+            .send_command(EdsCameraCommand::PressShutterButton, EdsShutterButton::Off)
+            .await;
+        pressed.and(released)
+    }
+
+    /// Downloads the `expected` files of the next shot reported to `events`.
+    ///
+    /// The files of a shot are told apart by the group of the first one
+    /// received: files of other groups are left to the camera with
+    /// `EdsDownloadCancel`. When the shot is not complete, its group is
+    /// remembered so that its late files are not taken for those of a later shot.
+    pub(crate) async fn receive_items(
+        &self,
+        mut events: broadcast::Receiver<CameraEvent>,
+        expected: usize,
+        deadline: Instant,
+    ) -> Result<Vec<CapturedItem>, Error> {
+        let mut group = None;
+        let mut items = Vec::with_capacity(expected);
+        let received = async {
+            while items.len() < expected {
+                let event = match time::timeout_at(deadline, events.recv()).await {
+                    Ok(Ok(event)) => event,
+                    Ok(Err(RecvError::Lagged(_))) => continue,
+                    Ok(Err(RecvError::Closed)) => return Err(EdsError::SessionNotOpen.into()),
+                    Err(_) => return Err(EdsError::WaitTimeoutError.into()),
+                };
+                match event {
+                    CameraEvent::Object {
+                        event: EdsObjectEvent::DirItemRequestTransfer,
+                        object: Some(EventObject::DirectoryItem(item)),
+                    } => {
+                        // An unreadable item is left to the camera; its shot is
+                        // recorded as abandoned below if a file of it was received.
+                        let info = match eds_get_directory_item_info(&item).await {
+                            Ok(info) => info,
+                            Err(err) => {
+                                let _ = eds_download_cancel(&item).await;
+                                return Err(err);
+                            }
+                        };
+                        let foreign = match group {
+                            Some(group) => info.group_id != group,
+                            None => self.abandoned_groups().contains(&info.group_id),
+                        };
+                        if foreign {
+                            let _ = eds_download_cancel(&item).await;
+                            continue;
+                        }
+                        group = Some(info.group_id);
+                        items.push(download_item(&item, info).await?);
+                    }
+                    CameraEvent::State {
+                        event: EdsStateEvent::CaptureError,
+                        data,
+                    } => return Err(capture_error(data)),
+                    CameraEvent::State {
+                        event: EdsStateEvent::Shutdown,
+                        ..
+                    } => return Err(EdsError::CommDisconnected.into()),
+                    _ => (),
+                }
+            }
+            Ok(())
+        }
+        .await;
+        if let (Err(_), Some(group)) = (&received, group)
+            && expected > 1
+            && group != 0
+        {
+            self.abandoned_groups().insert(group);
+        }
+        received.map(|()| items)
+    }
+
+    fn abandoned_groups(&self) -> std::sync::MutexGuard<'_, HashSet<EdsUInt32>> {
+        self.abandoned_groups
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
@@ -0,0 +1,362 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::{EDS_COMPONENTID_MASK, EDS_ISSPECIFIC_MASK, EdsComponentId, EdsError, EdsErrorCode};
+use std::fmt;
+
+/// Error returned by the functions of this crate.
+///
+/// Wraps the code returned by the SDK, decoded as an [`EdsError`], together with
+/// the call that failed when the error comes from the SDK itself. The raw code is
+/// kept, so component bits and IDs unknown to this crate are not lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    code: EdsError,
+    raw: EdsErrorCode,
+    operation: Option<&'static str>,
+}
+
+impl Error {
+    pub fn new(code: EdsError) -> Self {
+        Self {
+            code,
+            raw: code.into(),
+            operation: None,
+        }
+    }
+
+    /// Decodes a raw code returned by the SDK.
+    pub fn from_code(raw: EdsErrorCode) -> Self {
+        Self {
+            code: EdsError::from_code(raw),
+            raw,
+            operation: None,
+        }
+    }
+
+    /// Error returned by the SDK `operation` (e.g. `EdsOpenSession(...)`).
+    pub fn with_operation(raw: EdsErrorCode, operation: &'static str) -> Self {
+        Self {
+            operation: Some(operation),
+            ..Self::from_code(raw)
+        }
+    }
+
+    pub fn code(&self) -> EdsError {
+        self.code
+    }
+
+    /// Code as returned by the SDK, including the component and specific bits.
+    pub fn raw_code(&self) -> EdsErrorCode {
+        self.raw
+    }
+
+    /// Component of the SDK that raised the error.
+    pub fn component(&self) -> EdsComponentId {
+        EdsComponentId::from(self.raw & EDS_COMPONENTID_MASK)
+    }
+
+    /// Whether the error ID is specific to [`Error::component`].
+    pub fn is_specific(&self) -> bool {
+        self.raw & EDS_ISSPECIFIC_MASK != 0
+    }
+
+    /// SDK call that failed, if the error was returned by the SDK.
+    pub fn operation(&self) -> Option<&'static str> {
+        self.operation
+    }
+
+    /// The camera is busy; the same call is likely to succeed a bit later.
+    pub fn is_device_busy(&self) -> bool {
+        matches!(
+            self.code,
+            EdsError::DeviceBusy | EdsError::PtpDeviceBusy | EdsError::ObjectNotready
+        )
+    }
+
+    /// The call may succeed if retried without changing anything.
+    pub fn is_retryable(&self) -> bool {
+        self.is_device_busy()
+            || matches!(
+                self.code,
+                EdsError::WaitTimeoutError
+                    | EdsError::CommBufferFull
+                    | EdsError::MemorystatusNotready
+                    | EdsError::TakePictureAfNg
+                    | EdsError::TakePictureStroboChargeNg
+            )
+    }
+
+    /// The connection with the camera is lost; the session has to be reopened.
+    pub fn is_communication_lost(&self) -> bool {
+        matches!(
+            self.code,
+            EdsError::CommDisconnected
+                | EdsError::CommUsbBusErr
+                | EdsError::DeviceNotFound
+                | EdsError::SessionNotOpen
+        )
+    }
+
+    /// The camera refused to take a picture.
+    pub fn is_take_picture_failure(&self) -> bool {
+        matches!(
+            self.code,
+            EdsError::TakePictureAfNg
+                | EdsError::TakePictureReserved
+                | EdsError::TakePictureMirrorUpNg
+                | EdsError::TakePictureSensorCleaningNg
+                | EdsError::TakePictureSilenceNg
+                | EdsError::TakePictureNoCardNg
+                | EdsError::TakePictureCardNg
+                | EdsError::TakePictureCardProtectNg
+                | EdsError::TakePictureMovieCropNg
+                | EdsError::TakePictureStroboChargeNg
+                | EdsError::TakePictureNoLensNg
+                | EdsError::TakePictureSpecialMovieModeNg
+                | EdsError::TakePictureLvRelProhibitModeNg
+                | EdsError::TakePictureMovieModeNg
+                | EdsError::TakePictureRetructedLensNg
+        )
+    }
+
+    /// Error while accessing a file or a directory on the host.
+    pub fn is_file_io(&self) -> bool {
+        matches!(
+            self.code,
+            EdsError::FileIoError
+                | EdsError::FileTooManyOpen
+                | EdsError::FileNotFound
+                | EdsError::FileOpenError
+                | EdsError::FileCloseError
+                | EdsError::FileSeekError
+                | EdsError::FileTellError
+                | EdsError::FileReadError
+                | EdsError::FileWriteError
+                | EdsError::FilePermissionError
+                | EdsError::FileDiskFullError
+                | EdsError::FileAlreadyExists
+                | EdsError::FileFormatUnrecognized
+                | EdsError::FileDataCorrupt
+                | EdsError::FileNamingNa
+                | EdsError::DirNotFound
+                | EdsError::DirIoError
+                | EdsError::DirEntryNotFound
+                | EdsError::DirEntryExists
+                | EdsError::DirNotEmpty
+        )
+    }
+}
+
+impl From<EdsError> for Error {
+    fn from(code: EdsError) -> Self {
+        Self::new(code)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.operation {
+            Some(operation) => write!(f, "call to `{operation}` failed"),
+            None => write!(f, "{} ({:#010x})", self.code.message(), self.raw),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.operation {
+            Some(_) => Some(&self.code),
+            None => None,
+        }
+    }
+}
+
+impl EdsError {
+    /// Human readable description of the code.
+    pub fn message(&self) -> &'static str {
+        use EdsError::*;
+        match self {
+            Ok => "no error",
+            Unimplemented => "not implemented",
+            InternalError => "internal error",
+            MemAllocFailed => "memory allocation failed",
+            MemFreeFailed => "memory release failed",
+            OperationCancelled => "operation cancelled",
+            IncompatibleVersion => "incompatible version",
+            NotSupported => "not supported",
+            UnexpectedException => "unexpected exception",
+            ProtectionViolation => "protection violation",
+            MissingSubcomponent => "missing subcomponent",
+            SelectionUnavailable => "selection unavailable",
+            FileIoError => "file I/O error",
+            FileTooManyOpen => "too many files open",
+            FileNotFound => "file not found",
+            FileOpenError => "file open error",
+            FileCloseError => "file close error",
+            FileSeekError => "file seek error",
+            FileTellError => "file tell error",
+            FileReadError => "file read error",
+            FileWriteError => "file write error",
+            FilePermissionError => "file permission error",
+            FileDiskFullError => "disk full",
+            FileAlreadyExists => "file already exists",
+            FileFormatUnrecognized => "file format not recognized",
+            FileDataCorrupt => "file data corrupt",
+            FileNamingNa => "file naming not available",
+            DirNotFound => "directory not found",
+            DirIoError => "directory I/O error",
+            DirEntryNotFound => "directory entry not found",
+            DirEntryExists => "directory entry already exists",
+            DirNotEmpty => "directory not empty",
+            PropertiesUnavailable => "property not available",
+            PropertiesMismatch => "property type mismatch",
+            PropertiesNotLoaded => "property not loaded",
+            InvalidParameter => "invalid parameter",
+            InvalidHandle => "invalid handle",
+            InvalidPointer => "invalid pointer",
+            InvalidIndex => "invalid index",
+            InvalidLength => "invalid length",
+            InvalidFnPointer => "invalid function pointer",
+            InvalidSortFn => "invalid sort function",
+            DeviceNotFound => "device not found",
+            DeviceBusy => "device busy",
+            DeviceInvalid => "device invalid",
+            DeviceEmergency => "device emergency",
+            DeviceMemoryFull => "device memory full",
+            DeviceInternalError => "device internal error",
+            DeviceInvalidParameter => "device invalid parameter",
+            DeviceNoDisk => "no disk in device",
+            DeviceDiskError => "device disk error",
+            DeviceCfGateChanged => "CF gate changed",
+            DeviceDialChanged => "mode dial changed",
+            DeviceNotInstalled => "device not installed",
+            DeviceStayAwake => "device connected in awake mode",
+            DeviceNotReleased => "device not released",
+            StreamIoError => "stream I/O error",
+            StreamNotOpen => "stream not open",
+            StreamAlreadyOpen => "stream already open",
+            StreamOpenError => "stream open error",
+            StreamCloseError => "stream close error",
+            StreamSeekError => "stream seek error",
+            StreamTellError => "stream tell error",
+            StreamReadError => "stream read error",
+            StreamWriteError => "stream write error",
+            StreamPermissionError => "stream permission error",
+            StreamCouldntBeginThread => "could not start the stream thread",
+            StreamBadOptions => "invalid stream options",
+            StreamEndOfStream => "end of stream",
+            CommPortIsInUse => "port in use",
+            CommDisconnected => "camera disconnected",
+            CommDeviceIncompatible => "device incompatible",
+            CommBufferFull => "communication buffer full",
+            CommUsbBusErr => "USB bus error",
+            UsbDeviceLockError => "failed to lock the USB device UI",
+            UsbDeviceUnlockError => "failed to unlock the USB device UI",
+            StiUnknownError => "unknown STI error",
+            StiInternalError => "STI internal error",
+            StiDeviceCreateError => "STI device creation error",
+            StiDeviceReleaseError => "STI device release error",
+            DeviceNotLaunched => "device startup failed",
+            EnumNa => "enumeration terminated",
+            InvalidFnCall => "function called in an invalid sequence",
+            HandleNotFound => "handle not found",
+            InvalidId => "invalid ID",
+            WaitTimeoutError => "timeout",
+            SessionNotOpen => "session not open",
+            InvalidTransactionid => "invalid transaction ID",
+            IncompleteTransfer => "incomplete transfer",
+            InvalidStrageid => "invalid storage ID",
+            DevicepropNotSupported => "device property not supported",
+            InvalidObjectformatcode => "invalid object format code",
+            SelfTestFailed => "self test failed",
+            PartialDeletion => "partial deletion",
+            SpecificationByFormatUnsupported => "format specification unsupported",
+            NoValidObjectinfo => "no valid object information",
+            InvalidCodeFormat => "invalid code format",
+            UnknownVendorCode => "unknown vendor code",
+            CaptureAlreadyTerminated => "capture already terminated",
+            PtpDeviceBusy => "PTP device busy",
+            InvalidParentobject => "invalid parent object",
+            InvalidDevicepropFormat => "invalid device property format",
+            InvalidDevicepropValue => "invalid device property value",
+            SessionAlreadyOpen => "session already open",
+            TransactionCancelled => "transaction cancelled",
+            SpecificationOfDestinationUnsupported => "destination specification unsupported",
+            NotCameraSupportSdkVersion => "camera does not support this SDK version",
+            UnknownCommand => "unknown command",
+            OperationRefused => "operation refused",
+            LensCoverClose => "lens cover closed",
+            LowBattery => "low battery",
+            ObjectNotready => "image data not ready",
+            CannotMakeObject => "cannot make object",
+            MemorystatusNotready => "memory status not ready",
+            TakePictureAfNg => "focus failed",
+            TakePictureReserved => "reserved",
+            TakePictureMirrorUpNg => "mirror up in progress",
+            TakePictureSensorCleaningNg => "sensor cleaning in progress",
+            TakePictureSilenceNg => "silent operation in progress",
+            TakePictureNoCardNg => "no memory card",
+            TakePictureCardNg => "memory card error",
+            TakePictureCardProtectNg => "memory card write protected",
+            TakePictureMovieCropNg => "movie crop mode",
+            TakePictureStroboChargeNg => "flash charging",
+            TakePictureNoLensNg => "no lens",
+            TakePictureSpecialMovieModeNg => "special movie mode",
+            TakePictureLvRelProhibitModeNg => "live view release prohibited",
+            TakePictureMovieModeNg => "movie mode",
+            TakePictureRetructedLensNg => "lens retracted",
+            LastGenericErrorPlusOne | Unknown(_) => "unknown error",
+        }
+    }
+}
+
+impl fmt::Display for EdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:#010x})", self.message(), u32::from(*self))
+    }
+}
+
+impl std::error::Error for EdsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_id() {
+        assert_eq!(EdsError::from_code(0), EdsError::Ok);
+        assert_eq!(EdsError::from_code(0x0000_00C1), EdsError::CommDisconnected);
+        assert_eq!(EdsError::from_code(0x0000_A102), EdsError::ObjectNotready);
+        assert_eq!(EdsError::from_code(0x0000_1234), EdsError::Unknown(0x1234));
+    }
+
+    #[test]
+    fn component_and_specific_bits() {
+        let error = Error::from_code(0x8300_00C1);
+        assert_eq!(error.code(), EdsError::CommDisconnected);
+        assert_eq!(error.component(), EdsComponentId::CmpIdHlsdkComponentid);
+        assert!(error.is_specific());
+        assert_eq!(error.raw_code(), 0x8300_00C1);
+
+        let error = Error::from_code(0x0100_0002);
+        assert_eq!(error.code(), EdsError::InternalError);
+        assert_eq!(error.component(), EdsComponentId::CmpIdClientComponentid);
+        assert!(!error.is_specific());
+
+        let error = Error::from_code(0x0000_0007);
+        assert_eq!(error.component(), EdsComponentId::None);
+    }
+
+    #[test]
+    fn reserved_bits_are_ignored() {
+        assert_eq!(EdsError::from_code(0x0012_0007), EdsError::NotSupported);
+    }
+
+    #[test]
+    fn zero_error_id_is_not_ok() {
+        for code in [0x0200_0000, 0x8000_0000, 0x0001_0000] {
+            assert_eq!(EdsError::from_code(code), EdsError::Unknown(code));
+        }
+    }
+}
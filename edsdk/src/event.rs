@@ -0,0 +1,415 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::*;
+use std::sync::{Arc, Weak};
+use tokio::sync::broadcast;
+
+/// Number of events kept for a receiver of [`CameraSession::events`] lagging
+/// behind, before it starts missing some.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Object handed along with an object event, released when dropped.
+#[derive(Debug, Clone)]
+pub enum EventObject {
+    Volume(Volume),
+    DirectoryItem(DirectoryItem),
+}
+
+impl EventObject {
+    /// Wraps the reference received by an object event handler in the handle
+    /// matching `in_event`, kept by `sdk`. Returns `None` for a null reference,
+    /// and releases the reference of an unknown event, since its kind cannot be
+    /// told.
+    ///
+    /// # Safety
+    /// `in_ref` must be the reference passed by the SDK along with `in_event`,
+    /// and must not be released by any other mean.
+    pub(crate) unsafe fn from_event(
+        in_event: EdsObjectEvent,
+        in_ref: EdsBaseRef,
+        sdk: &Arc<SdkContext>,
+    ) -> Option<Self> {
+        use EdsObjectEvent::*;
+        if in_ref.0.is_null() {
+            return None;
+        }
+        let object = match in_event {
+            VolumeInfoChanged | VolumeUpdateItems | VolumeAdded | VolumeRemoved => {
+                let in_ref = unsafe { EdsVolumeRef::from_base(in_ref) };
+                EventObject::Volume(Volume::from_raw_in(in_ref, sdk.clone()))
+            }
+            Unknown(_) => {
+                unsafe { EdsRelease(in_ref) };
+                return None;
+            }
+            _ => {
+                let in_ref = unsafe { EdsDirectoryItemRef::from_base(in_ref) };
+                EventObject::DirectoryItem(DirectoryItem::from_raw_in(in_ref, sdk.clone()))
+            }
+        };
+        Some(object)
+    }
+}
+
+/// Event reported by the camera of a [`CameraSession`].
+#[derive(Debug, Clone)]
+pub enum CameraEvent {
+    Object {
+        event: EdsObjectEvent,
+        object: Option<EventObject>,
+    },
+    Property {
+        event: EdsPropertyEvent,
+        property_id: EdsPropertyID,
+        param: EdsUInt32,
+    },
+    State {
+        event: EdsStateEvent,
+        data: EdsUInt32,
+    },
+}
+
+type ObjectHandler = Arc<std::sync::Mutex<dyn FnMut(EdsObjectEvent, Option<EventObject>) + Send>>;
+type PropertyHandler =
+    Arc<std::sync::Mutex<dyn FnMut(EdsPropertyEvent, EdsPropertyID, EdsUInt32) + Send>>;
+type StateHandler = Arc<std::sync::Mutex<dyn FnMut(EdsStateEvent, EdsUInt32) + Send>>;
+
+#[derive(Clone)]
+enum Handler {
+    Object(ObjectHandler),
+    Property(PropertyHandler),
+    State(StateHandler),
+}
+
+impl Handler {
+    fn kind(&self) -> EventKind {
+        match self {
+            Handler::Object(_) => EventKind::Object,
+            Handler::Property(_) => EventKind::Property,
+            Handler::State(_) => EventKind::State,
+        }
+    }
+}
+
+/// Kind of events with its own SDK handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+    Object,
+    Property,
+    State,
+}
+
+impl EventKind {
+    const ALL: [EventKind; 3] = [EventKind::Object, EventKind::Property, EventKind::State];
+}
+
+#[derive(Default)]
+struct Handlers {
+    next_id: u64,
+    registered: Vec<(u64, Handler)>,
+    /// Kinds whose SDK handler is set, indexed by `EventKind`.
+    installed: [bool; 3],
+}
+
+impl Handlers {
+    fn count(&self, kind: EventKind) -> usize {
+        self.registered
+            .iter()
+            .filter(|(_, handler)| handler.kind() == kind)
+            .count()
+    }
+}
+
+impl std::fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handlers")
+            .field("registered", &self.registered.len())
+            .field("installed", &self.installed)
+            .finish()
+    }
+}
+
+fn call<F: ?Sized>(handler: &std::sync::Mutex<F>) -> std::sync::MutexGuard<'_, F> {
+    handler.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Receives the events of a session from the SDK and forwards them to the
+/// subscribers of [`CameraSession::events`] and to the registered handlers,
+/// after refreshing the property cache.
+///
+/// It is registered as the context of the session's event handlers, so it must
+/// outlive their registration.
+#[derive(Debug)]
+pub(crate) struct EventDispatcher {
+    sender: broadcast::Sender<CameraEvent>,
+    handlers: std::sync::Mutex<Handlers>,
+    /// Camera of the session, which also keeps the SDK of the objects received
+    /// with the events.
+    camera: Camera,
+    pub(crate) cache: PropertyCache,
+}
+
+impl EventDispatcher {
+    pub(crate) fn new(camera: &Camera) -> Arc<Self> {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Arc::new(Self {
+            sender,
+            handlers: Default::default(),
+            camera: camera.clone(),
+            cache: PropertyCache::new(camera),
+        })
+    }
+
+    fn handlers(&self) -> std::sync::MutexGuard<'_, Handlers> {
+        self.handlers.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn dispatch(&self, event: CameraEvent) {
+        if let CameraEvent::Property {
+            event, property_id, ..
+        } = event
+        {
+            self.cache.on_event(event, property_id);
+        }
+        // Called out of the lock, so that handlers can register or drop handlers.
+        let handlers: Vec<Handler> = self
+            .handlers()
+            .registered
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect();
+        for handler in handlers {
+            match (handler, &event) {
+                (Handler::Object(f), CameraEvent::Object { event, object }) => {
+                    call(&f)(*event, object.clone())
+                }
+                (
+                    Handler::Property(f),
+                    CameraEvent::Property {
+                        event,
+                        property_id,
+                        param,
+                    },
+                ) => call(&f)(*event, *property_id, *param),
+                (Handler::State(f), CameraEvent::State { event, data }) => call(&f)(*event, *data),
+                _ => (),
+            }
+        }
+        // No receiver is not an error: the event is simply dropped.
+        let _ = self.sender.send(event);
+    }
+
+    fn register(self: &Arc<Self>, handler: Handler) -> EventHandlerGuard {
+        with_event_lock(|| {
+            let mut handlers = self.handlers();
+            let id = handlers.next_id;
+            handlers.next_id += 1;
+            let kind = handler.kind();
+            handlers.registered.push((id, handler));
+            // Setting the SDK handler only fails once the session is unusable,
+            // which its other calls report.
+            let _ = self.set_installed(&mut handlers, kind, true);
+            EventHandlerGuard {
+                dispatcher: Arc::downgrade(self),
+                id,
+            }
+        })
+    }
+
+    /// Removes the handler `id`, and the SDK handler of its kind if nothing
+    /// depends on it anymore.
+    fn unregister_handler(&self, id: u64) -> Option<Handler> {
+        with_event_lock(|| {
+            let mut handlers = self.handlers();
+            let index = handlers.registered.iter().position(|(i, _)| *i == id)?;
+            let (_, handler) = handlers.registered.remove(index);
+            let kind = handler.kind();
+            if handlers.count(kind) == 0 && !self.is_needed(kind) {
+                let _ = self.set_installed(&mut handlers, kind, false);
+            }
+            Some(handler)
+        })
+    }
+
+    /// Whether the events of `kind` are needed besides the registered handlers:
+    /// by the receivers of [`CameraSession::events`], or by the property cache
+    /// once used.
+    fn is_needed(&self, kind: EventKind) -> bool {
+        self.sender.receiver_count() > 0 || (kind == EventKind::Property && self.cache.is_used())
+    }
+
+    /// Sets the SDK handler of `kind`, with `self` as context, or removes it
+    /// by passing `None`. Must be called with the event lock.
+    fn set_installed(
+        &self,
+        handlers: &mut Handlers,
+        kind: EventKind,
+        install: bool,
+    ) -> Result<(), Error> {
+        if handlers.installed[kind as usize] == install {
+            return Ok(());
+        }
+        let context = match install {
+            true => self as *const Self as *mut EdsVoid,
+            false => null_mut(),
+        };
+        let camera = self.camera.as_raw();
+        match kind {
+            EventKind::Object => check_call!(EdsSetObjectEventHandler(
+                camera,
+                EdsObjectEvent::All.into(),
+                install.then_some(object_handler),
+                context
+            )),
+            EventKind::Property => check_call!(EdsSetPropertyEventHandler(
+                camera,
+                EdsPropertyEvent::All.into(),
+                install.then_some(property_handler),
+                context
+            )),
+            EventKind::State => check_call!(EdsSetCameraStateEventHandler(
+                camera,
+                EdsStateEvent::All.into(),
+                install.then_some(state_handler),
+                context
+            )),
+        }?;
+        handlers.installed[kind as usize] = install;
+        Ok(())
+    }
+
+    /// Sets the SDK handlers of every kind of event not set yet.
+    pub(crate) fn install(&self) -> Result<(), Error> {
+        with_event_lock(|| {
+            let mut handlers = self.handlers();
+            EventKind::ALL
+                .into_iter()
+                .try_for_each(|kind| self.set_installed(&mut handlers, kind, true))
+        })
+    }
+
+    /// Unregisters the SDK handlers, waiting for the one in flight if any.
+    /// The dispatcher can be dropped once this has returned.
+    pub(crate) fn uninstall(&self) -> Result<(), Error> {
+        with_event_lock(|| {
+            let mut handlers = self.handlers();
+            let object = self.set_installed(&mut handlers, EventKind::Object, false);
+            let property = self.set_installed(&mut handlers, EventKind::Property, false);
+            let state = self.set_installed(&mut handlers, EventKind::State, false);
+            object.and(property).and(state)
+        })
+    }
+}
+
+unsafe extern "C" fn object_handler(
+    in_event: EdsUInt32,
+    in_ref: EdsBaseRef,
+    in_context: *mut EdsVoid,
+) -> EdsErrorCode {
+    let dispatcher = unsafe { &*(in_context as *const EventDispatcher) };
+    let event = EdsObjectEvent::from(in_event);
+    let object = unsafe { EventObject::from_event(event, in_ref, &dispatcher.camera.1) };
+    dispatcher.dispatch(CameraEvent::Object { event, object });
+    EdsError::Ok.into()
+}
+
+unsafe extern "C" fn property_handler(
+    in_event: EdsUInt32,
+    in_property_id: EdsUInt32,
+    in_param: EdsUInt32,
+    in_context: *mut EdsVoid,
+) -> EdsErrorCode {
+    let dispatcher = unsafe { &*(in_context as *const EventDispatcher) };
+    dispatcher.dispatch(CameraEvent::Property {
+        event: EdsPropertyEvent::from(in_event),
+        property_id: EdsPropertyID::from(in_property_id),
+        param: in_param,
+    });
+    EdsError::Ok.into()
+}
+
+unsafe extern "C" fn state_handler(
+    in_event: EdsUInt32,
+    in_event_data: EdsUInt32,
+    in_context: *mut EdsVoid,
+) -> EdsErrorCode {
+    let dispatcher = unsafe { &*(in_context as *const EventDispatcher) };
+    dispatcher.dispatch(CameraEvent::State {
+        event: EdsStateEvent::from(in_event),
+        data: in_event_data,
+    });
+    EdsError::Ok.into()
+}
+
+/// Registration of an event handler, removed when dropped.
+///
+/// Dropping the guard waits for the handler to return if it is running, and
+/// frees the closure. Dropping the last guard of a kind of event also removes
+/// the SDK handler of that kind, by passing `None` to `EdsSet*EventHandler`,
+/// unless receivers of [`CameraSession::events`] are alive or, for property
+/// events, the property cache is in use. It is set again by the next
+/// registration of that kind or call of [`CameraSession::events`].
+#[derive(Debug)]
+#[must_use = "the handler is removed when the guard is dropped"]
+pub struct EventHandlerGuard {
+    dispatcher: Weak<EventDispatcher>,
+    id: u64,
+}
+
+impl Drop for EventHandlerGuard {
+    fn drop(&mut self) {
+        if let Some(dispatcher) = self.dispatcher.upgrade() {
+            drop(dispatcher.unregister_handler(self.id));
+        }
+    }
+}
+
+impl CameraSession {
+    /// Calls `handler` with every object event of the camera, until the returned
+    /// guard is dropped.
+    ///
+    /// Handlers run on the thread calling `eds_get_event`.
+    pub fn set_object_handler<F>(&self, handler: F) -> EventHandlerGuard
+    where
+        F: FnMut(EdsObjectEvent, Option<EventObject>) + Send + 'static,
+    {
+        self.events
+            .register(Handler::Object(Arc::new(std::sync::Mutex::new(handler))))
+    }
+
+    /// Calls `handler` with every property event of the camera, until the returned
+    /// guard is dropped. See [`CameraSession::set_object_handler`].
+    pub fn set_property_handler<F>(&self, handler: F) -> EventHandlerGuard
+    where
+        F: FnMut(EdsPropertyEvent, EdsPropertyID, EdsUInt32) + Send + 'static,
+    {
+        self.events
+            .register(Handler::Property(Arc::new(std::sync::Mutex::new(handler))))
+    }
+
+    /// Calls `handler` with every state event of the camera, until the returned
+    /// guard is dropped. See [`CameraSession::set_object_handler`].
+    pub fn set_state_handler<F>(&self, handler: F) -> EventHandlerGuard
+    where
+        F: FnMut(EdsStateEvent, EdsUInt32) + Send + 'static,
+    {
+        self.events
+            .register(Handler::State(Arc::new(std::sync::Mutex::new(handler))))
+    }
+
+    /// Subscribes to the events of the camera.
+    ///
+    /// Events are only delivered while `eds_get_event` is being called, see
+    /// [`Sdk::with_event_pump`]. A receiver only gets the events sent after its
+    /// creation, and misses the oldest ones (`RecvError::Lagged`) if it falls
+    /// too far behind. The channel is closed when the session is.
+    pub fn events(&self) -> broadcast::Receiver<CameraEvent> {
+        let receiver = self.events.sender.subscribe();
+        // Sets again the SDK handlers removed with the last guard of their
+        // kind, see `EventHandlerGuard`. This only fails once the session is
+        // unusable, which its other calls report.
+        let _ = self.events.install();
+        receiver
+    }
+}
@@ -0,0 +1,484 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! Physical values of the exposure settings.
+
+use crate::*;
+use std::{cmp::Ordering, fmt, time::Duration};
+
+/// Fraction of a stop the camera moves by at each click of a dial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopIncrement {
+    Third,
+    Half,
+    Full,
+}
+
+impl StopIncrement {
+    pub fn stops(self) -> f64 {
+        match self {
+            StopIncrement::Third => 1.0 / 3.0,
+            StopIncrement::Half => 0.5,
+            StopIncrement::Full => 1.0,
+        }
+    }
+}
+
+/// Exposure setting with a position on a scale of stops.
+///
+/// Settings are ordered by their physical value, e.g. the f-number for
+/// [`EdsAv`] or the exposure time for [`EdsTv`], so that stepping up a
+/// shutter speed makes it longer.
+pub trait Stops: Copy + Ord {
+    /// Position in stops, increasing with the physical value. `None` for
+    /// settings without one, such as [`EdsISO::Auto`] or [`EdsTv::TvBulb`].
+    fn stops(self) -> Option<f64>;
+
+    /// Value of `allowed` closest to `steps` increments away from `self`, e.g.
+    /// among the values of a [`PropertyDescriptor`]. Negative steps go down.
+    ///
+    /// Stays on the closest allowed value at the ends of the range. `None` if
+    /// `self` or every allowed value has no position.
+    fn step(self, steps: i32, increment: StopIncrement, allowed: &[Self]) -> Option<Self> {
+        self.shift(f64::from(steps) * increment.stops(), allowed)
+    }
+
+    /// Value of `allowed` closest to `stops` away from `self`, for offsets that
+    /// are not a whole number of increments. See [`Stops::step`].
+    fn shift(self, stops: f64, allowed: &[Self]) -> Option<Self> {
+        nearest(self.stops()? + stops, allowed)
+    }
+}
+
+/// Value of `allowed` whose position is the closest to `target`.
+fn nearest<T: Stops>(target: f64, allowed: &[T]) -> Option<T> {
+    allowed
+        .iter()
+        .filter_map(|value| Some((*value, (value.stops()? - target).abs())))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(value, _)| value)
+}
+
+/// Orders settings by their physical value, then by code for the 1/2 and 1/3
+/// stop variants of the same value.
+macro_rules! order_by_value {
+    ($($t:ty: $value:expr;)*) => {$(
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $t {
+            fn cmp(&self, other: &Self) -> Ordering {
+                let value: fn($t) -> f64 = $value;
+                value(*self)
+                    .total_cmp(&value(*other))
+                    .then_with(|| u32::from(*self).cmp(&u32::from(*other)))
+            }
+        }
+    )*};
+}
+
+order_by_value! {
+    EdsAv: EdsAv::f_number;
+    EdsTv: |tv| tv.seconds().unwrap_or(f64::INFINITY);
+    EdsISO: |iso| iso.number().map_or(0.0, f64::from);
+    EdsExposureCompensation: EdsExposureCompensation::ev;
+}
+
+macro_rules! av_table {
+    ($($av:ident: $f_number:literal;)*) => {
+        impl EdsAv {
+            /// Nominal f-number, e.g. `5.6` for [`EdsAv::Av5_6`].
+            pub fn f_number(self) -> f64 {
+                match self {
+                    $(EdsAv::$av => $f_number,)*
+                }
+            }
+        }
+    };
+}
+
+// Codes sharing an f-number are the 1/2 and 1/3 stop variants of the same
+// aperture.
+av_table! {
+    Av1: 1.0;
+    Av1_1: 1.1;
+    Av1_2: 1.2;
+    Av1_2_3: 1.2;
+    Av1_4: 1.4;
+    Av1_6: 1.6;
+    Av1_8: 1.8;
+    Av1_8_3: 1.8;
+    Av2: 2.0;
+    Av2_2: 2.2;
+    Av2_5: 2.5;
+    Av2_5_3: 2.5;
+    Av2_8: 2.8;
+    Av3_2: 3.2;
+    Av3_4: 3.4;
+    Av3_5: 3.5;
+    Av3_5_3: 3.5;
+    Av4: 4.0;
+    Av4_5: 4.5;
+    Av5_0: 5.0;
+    Av5_6: 5.6;
+    Av6_3: 6.3;
+    Av6_7: 6.7;
+    Av7_1: 7.1;
+    Av8: 8.0;
+    Av9: 9.0;
+    Av9_5: 9.5;
+    Av10: 10.0;
+    Av11: 11.0;
+    Av13_3: 13.0;
+    Av13: 13.0;
+    Av14: 14.0;
+    Av16: 16.0;
+    Av18: 18.0;
+    Av19: 19.0;
+    Av20: 20.0;
+    Av22: 22.0;
+    Av25: 25.0;
+    Av27: 27.0;
+    Av29: 29.0;
+    Av32: 32.0;
+    Av36: 36.0;
+    Av38: 38.0;
+    Av40: 40.0;
+    Av45: 45.0;
+    Av51: 51.0;
+    Av54: 54.0;
+    Av57: 57.0;
+    Av64: 64.0;
+    Av72: 72.0;
+    Av76: 76.0;
+    Av80: 80.0;
+    Av91: 91.0;
+}
+
+impl Stops for EdsAv {
+    fn stops(self) -> Option<f64> {
+        Some(2.0 * self.f_number().log2())
+    }
+}
+
+macro_rules! tv_table {
+    ($($tv:ident: $seconds:expr, $label:literal;)*) => {
+        impl EdsTv {
+            /// Every shutter speed but bulb, from the slowest to the fastest.
+            pub const SPEEDS: &'static [EdsTv] = &[$(EdsTv::$tv),*];
+
+            /// Nominal exposure time, in seconds. `None` for bulb.
+            pub fn seconds(self) -> Option<f64> {
+                match self {
+                    EdsTv::TvBulb => None,
+                    $(EdsTv::$tv => Some($seconds),)*
+                }
+            }
+
+            /// Shutter speed as displayed by the camera, e.g. `1/250` or `0"5`.
+            pub fn label(self) -> &'static str {
+                match self {
+                    EdsTv::TvBulb => "BULB",
+                    $(EdsTv::$tv => $label,)*
+                }
+            }
+        }
+    };
+}
+
+// Codes sharing a label are the 1/2 and 1/3 stop variants of the same speed.
+tv_table! {
+    Tv30s: 30.0, "30\"";
+    Tv25s: 25.0, "25\"";
+    Tv20s: 20.0, "20\"";
+    Tv20s3: 20.0, "20\"";
+    Tv15s: 15.0, "15\"";
+    Tv13s: 13.0, "13\"";
+    Tv10s: 10.0, "10\"";
+    Tv10s3: 10.0, "10\"";
+    Tv8s: 8.0, "8\"";
+    Tv6s3: 6.0, "6\"";
+    Tv6s: 6.0, "6\"";
+    Tv5s: 5.0, "5\"";
+    Tv4s: 4.0, "4\"";
+    Tv3s2: 3.2, "3\"2";
+    Tv3s: 3.0, "3\"";
+    Tv2s5: 2.5, "2\"5";
+    Tv2s: 2.0, "2\"";
+    Tv1s6: 1.6, "1\"6";
+    Tv1s5: 1.5, "1\"5";
+    Tv1s3: 1.3, "1\"3";
+    Tv1s: 1.0, "1\"";
+    Tv0s8: 0.8, "0\"8";
+    Tv0s7: 0.7, "0\"7";
+    Tv0s6: 0.6, "0\"6";
+    Tv0s5: 0.5, "0\"5";
+    Tv0s4: 0.4, "0\"4";
+    Tv0s3: 0.3, "0\"3";
+    Tv0s3_3: 0.3, "0\"3";
+    Tv1d4: 1.0 / 4.0, "1/4";
+    Tv1d5: 1.0 / 5.0, "1/5";
+    Tv1d6: 1.0 / 6.0, "1/6";
+    Tv1d6_3: 1.0 / 6.0, "1/6";
+    Tv1d8: 1.0 / 8.0, "1/8";
+    Tv1d10_3: 1.0 / 10.0, "1/10";
+    Tv1d10: 1.0 / 10.0, "1/10";
+    Tv1d13: 1.0 / 13.0, "1/13";
+    Tv1d15: 1.0 / 15.0, "1/15";
+    Tv1d20_3: 1.0 / 20.0, "1/20";
+    Tv1d20: 1.0 / 20.0, "1/20";
+    Tv1d25: 1.0 / 25.0, "1/25";
+    Tv1d30: 1.0 / 30.0, "1/30";
+    Tv1d40: 1.0 / 40.0, "1/40";
+    Tv1d45: 1.0 / 45.0, "1/45";
+    Tv1d50: 1.0 / 50.0, "1/50";
+    Tv1d60: 1.0 / 60.0, "1/60";
+    Tv1d80: 1.0 / 80.0, "1/80";
+    Tv1d90: 1.0 / 90.0, "1/90";
+    Tv1d100: 1.0 / 100.0, "1/100";
+    Tv1d125: 1.0 / 125.0, "1/125";
+    Tv1d160: 1.0 / 160.0, "1/160";
+    Tv1d180: 1.0 / 180.0, "1/180";
+    Tv1d200: 1.0 / 200.0, "1/200";
+    Tv1d250: 1.0 / 250.0, "1/250";
+    Tv1d320: 1.0 / 320.0, "1/320";
+    Tv1d350: 1.0 / 350.0, "1/350";
+    Tv1d400: 1.0 / 400.0, "1/400";
+    Tv1d500: 1.0 / 500.0, "1/500";
+    Tv1d640: 1.0 / 640.0, "1/640";
+    Tv1d750: 1.0 / 750.0, "1/750";
+    Tv1d800: 1.0 / 800.0, "1/800";
+    Tv1d1000: 1.0 / 1000.0, "1/1000";
+    Tv1d1250: 1.0 / 1250.0, "1/1250";
+    Tv1d1500: 1.0 / 1500.0, "1/1500";
+    Tv1d1600: 1.0 / 1600.0, "1/1600";
+    Tv1d2000: 1.0 / 2000.0, "1/2000";
+    Tv1d2500: 1.0 / 2500.0, "1/2500";
+    Tv1d3000: 1.0 / 3000.0, "1/3000";
+    Tv1d3200: 1.0 / 3200.0, "1/3200";
+    Tv1d4000: 1.0 / 4000.0, "1/4000";
+    Tv1d5000: 1.0 / 5000.0, "1/5000";
+    Tv1d6000: 1.0 / 6000.0, "1/6000";
+    Tv1d6400: 1.0 / 6400.0, "1/6400";
+    Tv1d8000: 1.0 / 8000.0, "1/8000";
+    Tv1d10000: 1.0 / 10000.0, "1/10000";
+    Tv1d12800: 1.0 / 12800.0, "1/12800";
+    Tv1d16000: 1.0 / 16000.0, "1/16000";
+    Tv1d20000: 1.0 / 20000.0, "1/20000";
+    Tv1d25600: 1.0 / 25600.0, "1/25600";
+    Tv1d32000: 1.0 / 32000.0, "1/32000";
+}
+
+impl EdsTv {
+    /// Nominal exposure time. `None` for bulb.
+    pub fn duration(self) -> Option<Duration> {
+        self.seconds().map(Duration::from_secs_f64)
+    }
+
+    /// Shutter speed closest to `seconds`, in stops. `None` unless `seconds` is
+    /// strictly positive.
+    pub fn from_seconds(seconds: f64) -> Option<Self> {
+        Self::nearest_in(seconds, Self::SPEEDS)
+    }
+
+    /// Shutter speed closest to `duration`, in stops.
+    pub fn from_duration(duration: Duration) -> Option<Self> {
+        Self::from_seconds(duration.as_secs_f64())
+    }
+
+    /// Shutter speed of `allowed` closest to `seconds`, in stops, e.g. among the
+    /// values of a [`PropertyDescriptor`]. Bulb is never picked.
+    pub fn nearest_in(seconds: f64, allowed: &[EdsTv]) -> Option<Self> {
+        if !(seconds.is_finite() && seconds > 0.0) {
+            return None;
+        }
+        nearest(seconds.log2(), allowed)
+    }
+}
+
+impl Stops for EdsTv {
+    fn stops(self) -> Option<f64> {
+        self.seconds().map(f64::log2)
+    }
+}
+
+impl fmt::Display for EdsTv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+macro_rules! iso_table {
+    ($($iso:ident: $number:literal;)*) => {
+        impl EdsISO {
+            /// ISO speed, e.g. `100` for [`EdsISO::ISO100`]. `None` for auto.
+            pub fn number(self) -> Option<u32> {
+                match self {
+                    EdsISO::Auto => None,
+                    $(EdsISO::$iso => Some($number),)*
+                }
+            }
+        }
+    };
+}
+
+iso_table! {
+    ISO6: 6;
+    ISO12: 12;
+    ISO25: 25;
+    ISO50: 50;
+    ISO100: 100;
+    ISO125: 125;
+    ISO160: 160;
+    ISO200: 200;
+    ISO250: 250;
+    ISO320: 320;
+    ISO400: 400;
+    ISO500: 500;
+    ISO640: 640;
+    ISO800: 800;
+    ISO1000: 1000;
+    ISO1250: 1250;
+    ISO1600: 1600;
+    ISO2000: 2000;
+    ISO2500: 2500;
+    ISO3200: 3200;
+    ISO4000: 4000;
+    ISO5000: 5000;
+    ISO6400: 6400;
+    ISO8000: 8000;
+    ISO10000: 10000;
+    ISO12800: 12800;
+    ISO16000: 16000;
+    ISO20000: 20000;
+    ISO25600: 25600;
+    ISO32000: 32000;
+    ISO40000: 40000;
+    ISO51200: 51200;
+    ISO64000: 64000;
+    ISO80000: 80000;
+    ISO102400: 102400;
+    ISO204800: 204800;
+    ISO409600: 409600;
+    ISO819200: 819200;
+}
+
+impl Stops for EdsISO {
+    fn stops(self) -> Option<f64> {
+        self.number()
+            .map(|number| (f64::from(number) / 100.0).log2())
+    }
+}
+
+impl EdsExposureCompensation {
+    /// Offset in EV, e.g. `-2.0 / 3.0` for [`EdsExposureCompensation::Minus2_3`].
+    pub fn ev(self) -> f64 {
+        let eighths = i32::from(u32::from(self) as u8 as i8);
+        let (whole, rest) = (eighths / 8, eighths % 8);
+        // Computed as a single fraction, so that e.g. -1 2/3 is exactly -5/3.
+        match rest.abs() {
+            3 => f64::from(3 * whole + rest.signum()) / 3.0,
+            4 => f64::from(2 * whole + rest.signum()) / 2.0,
+            5 => f64::from(3 * whole + 2 * rest.signum()) / 3.0,
+            _ => f64::from(whole),
+        }
+    }
+}
+
+impl Stops for EdsExposureCompensation {
+    fn stops(self) -> Option<f64> {
+        Some(self.ev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APERTURES: &[EdsAv] = &[
+        EdsAv::Av5_0,
+        EdsAv::Av5_6,
+        EdsAv::Av6_3,
+        EdsAv::Av6_7,
+        EdsAv::Av7_1,
+    ];
+
+    #[test]
+    fn av_steps_by_thirds() {
+        let av = EdsAv::Av5_6;
+        assert_eq!(
+            av.step(1, StopIncrement::Third, APERTURES),
+            Some(EdsAv::Av6_3)
+        );
+        assert_eq!(
+            av.step(-1, StopIncrement::Third, APERTURES),
+            Some(EdsAv::Av5_0)
+        );
+        assert_eq!(
+            av.step(3, StopIncrement::Third, APERTURES),
+            Some(EdsAv::Av7_1)
+        );
+    }
+
+    #[test]
+    fn tv_steps_by_stops() {
+        let tv = EdsTv::Tv1d250;
+        // Stepping up makes the exposure longer.
+        assert_eq!(
+            tv.step(1, StopIncrement::Full, EdsTv::SPEEDS),
+            Some(EdsTv::Tv1d125)
+        );
+        assert_eq!(
+            tv.step(-1, StopIncrement::Full, EdsTv::SPEEDS),
+            Some(EdsTv::Tv1d500)
+        );
+        assert_eq!(tv.shift(0.0, EdsTv::SPEEDS), Some(tv));
+    }
+
+    #[test]
+    fn step_stays_in_range() {
+        assert_eq!(
+            EdsAv::Av5_6.step(12, StopIncrement::Full, APERTURES),
+            Some(EdsAv::Av7_1)
+        );
+        assert_eq!(EdsAv::Av5_6.step(1, StopIncrement::Full, &[]), None);
+        assert_eq!(
+            EdsTv::TvBulb.step(1, StopIncrement::Full, EdsTv::SPEEDS),
+            None
+        );
+    }
+
+    #[test]
+    fn exposure_compensation_ev() {
+        assert_eq!(EdsExposureCompensation::Zero.ev(), 0.0);
+        assert_eq!(EdsExposureCompensation::Plus1_1_2.ev(), 1.5);
+        assert_eq!(EdsExposureCompensation::Minus1_2_3.ev(), -5.0 / 3.0);
+        assert_eq!(EdsExposureCompensation::Minus2_3.ev(), -2.0 / 3.0);
+        assert_eq!(EdsExposureCompensation::Minus5.ev(), -5.0);
+    }
+
+    #[test]
+    fn tv_from_seconds() {
+        assert_eq!(EdsTv::from_seconds(1.0 / 250.0), Some(EdsTv::Tv1d250));
+        assert_eq!(EdsTv::from_seconds(30.0), Some(EdsTv::Tv30s));
+        assert_eq!(EdsTv::from_seconds(0.0), None);
+        assert_eq!(
+            EdsTv::from_duration(Duration::from_millis(4)),
+            Some(EdsTv::Tv1d250)
+        );
+    }
+
+    #[test]
+    fn bulb_is_ordered_last() {
+        let mut speeds = vec![EdsTv::Tv30s, EdsTv::TvBulb, EdsTv::Tv1d250, EdsTv::Tv1s];
+        speeds.sort();
+        assert_eq!(
+            speeds,
+            [EdsTv::Tv1d250, EdsTv::Tv1s, EdsTv::Tv30s, EdsTv::TvBulb]
+        );
+        assert_eq!(EdsTv::SPEEDS.iter().max(), Some(&EdsTv::Tv30s));
+    }
+}
@@ -0,0 +1,360 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! Intervalometer shooting frames at a fixed interval.
+
+use crate::*;
+use std::time::Duration;
+use tokio::{
+    sync::{
+        broadcast::{
+            self,
+            error::{RecvError, TryRecvError},
+        },
+        mpsc,
+    },
+    time::{self, Instant},
+};
+
+/// When an intervalometer stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalEnd {
+    /// After this number of frames, skipped ones included.
+    Count(usize),
+    /// Before the first frame scheduled at or after this instant.
+    At(Instant),
+}
+
+/// What the intervalometer does with a frame due while the camera is still
+/// processing a previous one, i.e. while its job status is not zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BusyPolicy {
+    /// Reports the frame as skipped and waits for the next one.
+    #[default]
+    Skip,
+    /// Shoots the frame as soon as the camera is idle, failing it with
+    /// `EdsError::WaitTimeoutError` if it stays busy longer than the capture
+    /// timeout.
+    Delay,
+}
+
+/// Configuration of [`CameraSession::run_intervalometer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntervalometerConfig {
+    /// Delay between the scheduled times of two frames.
+    pub interval: Duration,
+    pub end: IntervalEnd,
+    pub busy: BusyPolicy,
+    /// Sends `ExtendShutDownTimer` before each frame and whenever the camera
+    /// warns it will soon shut down, so that the body does not fall asleep
+    /// between long intervals.
+    pub keep_awake: bool,
+    /// Configuration of the capture of each frame.
+    pub capture: CaptureConfig,
+}
+
+impl IntervalometerConfig {
+    pub fn new(interval: Duration, end: IntervalEnd) -> Self {
+        Self {
+            interval,
+            end,
+            busy: BusyPolicy::default(),
+            keep_awake: true,
+            capture: CaptureConfig::default(),
+        }
+    }
+}
+
+/// Exposure settings applied before a frame, the `None` ones being left unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameSettings {
+    pub tv: Option<EdsTv>,
+    pub av: Option<EdsAv>,
+    pub iso: Option<EdsISO>,
+    pub exposure_compensation: Option<EdsExposureCompensation>,
+}
+
+impl FrameSettings {
+    async fn apply(self, session: &CameraSession) -> Result<(), Error> {
+        if let Some(tv) = self.tv {
+            session.set_tv(tv).await?;
+        }
+        if let Some(av) = self.av {
+            session.set_av(av).await?;
+        }
+        if let Some(iso) = self.iso {
+            session.set_iso(iso).await?;
+        }
+        if let Some(compensation) = self.exposure_compensation {
+            session.set_exposure_compensation(compensation).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum FrameOutcome {
+    Captured(Vec<CapturedItem>),
+    /// The camera was busy when the frame was due, see [`BusyPolicy::Skip`].
+    Skipped,
+    /// The settings could not be applied or the capture failed. The run stops
+    /// after this frame if the connection is lost, see
+    /// [`Error::is_communication_lost`].
+    Failed(Error),
+}
+
+/// Frame reported by [`CameraSession::run_intervalometer`].
+#[derive(Debug)]
+pub struct Frame {
+    /// Index of the frame, from 0.
+    pub index: usize,
+    pub scheduled: Instant,
+    /// Delay between the scheduled time and the shutter release, or the skip.
+    pub drift: Duration,
+    pub outcome: FrameOutcome,
+    /// Statistics of the run up to and including this frame.
+    pub stats: IntervalStats,
+}
+
+/// Running statistics of an intervalometer run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntervalStats {
+    pub captured: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// Sum of the drifts of the frames captured or failed.
+    pub total_drift: Duration,
+    pub max_drift: Duration,
+}
+
+impl IntervalStats {
+    /// Mean drift of the frames captured or failed.
+    pub fn mean_drift(&self) -> Duration {
+        match self.captured + self.failed {
+            0 => Duration::ZERO,
+            shot => self.total_drift / shot as u32,
+        }
+    }
+
+    fn record(&mut self, drift: Duration, outcome: &FrameOutcome) {
+        match outcome {
+            FrameOutcome::Captured(_) => self.captured += 1,
+            FrameOutcome::Skipped => {
+                self.skipped += 1;
+                return;
+            }
+            FrameOutcome::Failed(_) => self.failed += 1,
+        }
+        self.total_drift += drift;
+        self.max_drift = self.max_drift.max(drift);
+    }
+}
+
+fn elapsed_since(instant: Instant) -> Duration {
+    Instant::now().saturating_duration_since(instant)
+}
+
+/// State of the camera followed from its events between frames.
+struct CameraState {
+    events: broadcast::Receiver<CameraEvent>,
+    job_status: EdsUInt32,
+    keep_awake: bool,
+}
+
+impl CameraState {
+    fn new(session: &CameraSession, keep_awake: bool) -> Self {
+        Self {
+            events: session.events(),
+            job_status: 0,
+            keep_awake,
+        }
+    }
+
+    async fn handle(
+        &mut self,
+        session: &CameraSession,
+        event: Result<CameraEvent, RecvError>,
+    ) -> Result<(), Error> {
+        match event {
+            Ok(CameraEvent::State {
+                event: EdsStateEvent::JobStatusChanged,
+                data,
+            }) => self.job_status = data,
+            Ok(CameraEvent::State {
+                event: EdsStateEvent::WillSoonShutDown,
+                ..
+            }) if self.keep_awake => {
+                let _ = session
+                    .send_command(EdsCameraCommand::ExtendShutDownTimer, 0)
+                    .await;
+            }
+            Ok(CameraEvent::State {
+                event: EdsStateEvent::Shutdown,
+                ..
+            }) => return Err(EdsError::CommDisconnected.into()),
+            // The last job status may have been missed: assume the camera is
+            // idle rather than waiting forever, a busy camera failing the shot.
+            Err(RecvError::Lagged(_)) => self.job_status = 0,
+            Err(RecvError::Closed) => return Err(EdsError::SessionNotOpen.into()),
+            Ok(_) => (),
+        }
+        Ok(())
+    }
+
+    /// Handles the events received so far.
+    async fn update(&mut self, session: &CameraSession) -> Result<(), Error> {
+        loop {
+            let event = match self.events.try_recv() {
+                Ok(event) => Ok(event),
+                Err(TryRecvError::Lagged(skipped)) => Err(RecvError::Lagged(skipped)),
+                Err(TryRecvError::Closed) => Err(RecvError::Closed),
+                Err(TryRecvError::Empty) => return Ok(()),
+            };
+            self.handle(session, event).await?;
+        }
+    }
+
+    /// Handles the events until `deadline`.
+    async fn wait_until(
+        &mut self,
+        session: &CameraSession,
+        deadline: Instant,
+    ) -> Result<(), Error> {
+        loop {
+            let event = match time::timeout_at(deadline, self.events.recv()).await {
+                Ok(event) => event,
+                Err(_) => return self.update(session).await,
+            };
+            self.handle(session, event).await?;
+        }
+    }
+
+    /// Handles the events until the job status is zero, failing with
+    /// `EdsError::WaitTimeoutError` at `deadline`.
+    async fn wait_idle(&mut self, session: &CameraSession, deadline: Instant) -> Result<(), Error> {
+        while self.job_status != 0 {
+            let event = time::timeout_at(deadline, self.events.recv())
+                .await
+                .map_err(|_| Error::new(EdsError::WaitTimeoutError))?;
+            self.handle(session, event).await?;
+        }
+        Ok(())
+    }
+}
+
+impl CameraSession {
+    /// Runs an intervalometer without changing the settings between frames.
+    /// See [`CameraSession::run_intervalometer_with`].
+    pub fn run_intervalometer(
+        self: &Arc<Self>,
+        config: IntervalometerConfig,
+    ) -> Result<mpsc::UnboundedReceiver<Frame>, Error> {
+        self.run_intervalometer_with(config, |_| FrameSettings::default())
+    }
+
+    /// Starts a task shooting a frame every `config.interval` until
+    /// `config.end`, applying `settings(index)` before each frame, e.g. to ramp
+    /// the exposure during a sunset. Each frame is sent to the returned
+    /// receiver once downloaded, with the statistics of the run so far.
+    ///
+    /// Frames are scheduled from the start of the run, so that a late frame
+    /// does not delay the next ones; frames longer than the interval make the
+    /// following ones late rather than skipped. Failed frames are reported and
+    /// the run continues, unless the connection is lost (see
+    /// [`Error::is_communication_lost`]). Captures are made as by
+    /// [`CameraSession::capture_with`], with the same requirements.
+    ///
+    /// The task holds a reference to the session, which stays open until the
+    /// run ends. The channel is closed when the run ends, and the run stops once
+    /// the receiver is dropped. The channel is unbounded so that a slow receiver
+    /// does not delay the frames. Fails with `EdsError::InvalidParameter` if
+    /// the interval is zero, and with `EdsError::InvalidFnCall` outside of a
+    /// tokio runtime.
+    pub fn run_intervalometer_with<S>(
+        self: &Arc<Self>,
+        config: IntervalometerConfig,
+        settings: S,
+    ) -> Result<mpsc::UnboundedReceiver<Frame>, Error>
+    where
+        S: FnMut(usize) -> FrameSettings + Send + 'static,
+    {
+        if config.interval.is_zero() {
+            return Err(EdsError::InvalidParameter.into());
+        }
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| Error::new(EdsError::InvalidFnCall))?;
+        let (sender, frames) = mpsc::unbounded_channel();
+        let session = self.clone();
+        runtime.spawn(async move { session.shoot_intervals(config, settings, sender).await });
+        Ok(frames)
+    }
+
+    async fn shoot_intervals<S>(
+        &self,
+        config: IntervalometerConfig,
+        mut settings: S,
+        frames: mpsc::UnboundedSender<Frame>,
+    ) where
+        S: FnMut(usize) -> FrameSettings,
+    {
+        let mut state = CameraState::new(self, config.keep_awake);
+        let mut stats = IntervalStats::default();
+        let mut scheduled = Instant::now();
+        for index in 0.. {
+            match config.end {
+                IntervalEnd::Count(count) if index >= count => break,
+                IntervalEnd::At(end) if scheduled >= end => break,
+                _ => (),
+            }
+            let waited = state.wait_until(self, scheduled).await;
+            let (drift, outcome) = if let Err(err) = waited {
+                (elapsed_since(scheduled), FrameOutcome::Failed(err))
+            } else if state.job_status != 0 && config.busy == BusyPolicy::Skip {
+                (elapsed_since(scheduled), FrameOutcome::Skipped)
+            } else {
+                let prepared = self
+                    .prepare_frame(&mut state, &config, settings(index))
+                    .await;
+                let drift = elapsed_since(scheduled);
+                let captured = match prepared {
+                    Ok(()) => self.capture_with(config.capture).await,
+                    Err(err) => Err(err),
+                };
+                match captured {
+                    Ok(items) => (drift, FrameOutcome::Captured(items)),
+                    Err(err) => (drift, FrameOutcome::Failed(err)),
+                }
+            };
+            let lost = matches!(&outcome, FrameOutcome::Failed(err) if err.is_communication_lost());
+            stats.record(drift, &outcome);
+            let frame = Frame {
+                index,
+                scheduled,
+                drift,
+                outcome,
+                stats,
+            };
+            if frames.send(frame).is_err() || lost {
+                break;
+            }
+            scheduled += config.interval;
+        }
+    }
+
+    /// Waits for the camera to be idle, then applies the settings of a frame.
+    async fn prepare_frame(
+        &self,
+        state: &mut CameraState,
+        config: &IntervalometerConfig,
+        settings: FrameSettings,
+    ) -> Result<(), Error> {
+        state
+            .wait_idle(self, Instant::now() + config.capture.timeout)
+            .await?;
+        if config.keep_awake {
+            self.send_command(EdsCameraCommand::ExtendShutDownTimer, 0)
+                .await?;
+        }
+        settings.apply(self).await
+    }
+}
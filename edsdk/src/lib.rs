@@ -8,6 +8,16 @@
 //! - They must be properly released once they are no longer needed.
 //! - They must not be used or modified after being released.
 //!
+//! Objects returned by the SDK are wrapped in owned handles ([`CameraList`],
+//! [`Camera`], [`Volume`], [`DirectoryItem`], [`Stream`], [`Image`] and
+//! [`EvfImage`]) that call `EdsRelease` when dropped, and `EdsRetain` when cloned.
+//! They all keep the SDK initialized: `EdsTerminateSDK` is only called once the
+//! [`Sdk`] guard and every handle have been dropped.
+//!
+//! Calls that need an open session on the camera (properties, commands, event
+//! handlers, live view) take a [`CameraSession`], obtained with
+//! [`Camera::open_session`] and closed when dropped.
+//!
 //! In some rare cases, the SDK may expect a pointer to a variable or structure
 //! allocated by the user. However, the library usually provides helper functions
 //! to allocate or initialize such objects safely.
@@ -18,26 +28,92 @@
 //! ## Pointer Type Safety
 //!
 //! From the SDK's point of view, a pointer is just a raw memory address. The C interface
-//! does not enforce strong typing, and Rust’s static analysis cannot guarantee that
-//! the pointer types match the SDK’s expectations.
+//! does not enforce strong typing: every reference is an alias of `EdsBaseRef`.
+//!
+//! This crate gives each kind of reference its own type (`EdsCameraRef`,
+//! `EdsDirectoryItemRef`, ...), so passing a camera to `eds_delete_directory_item`
+//! does not compile. Converting to `EdsBaseRef` is always possible, while the reverse
+//! conversion, `from_base`, is `unsafe` since the SDK cannot tell the kind of an object.
+//!
+//! Where the SDK genuinely accepts several kinds of objects, the functions are generic
+//! over a trait implemented only by the accepted handles: [`EdsPropertyHolder`] for the
+//! property functions (camera sessions, images and live view images) and [`EdsProgressSource`] for
+//! `eds_set_progress_callback` (streams and images).
+//!
+//! ## Properties
+//!
+//! [`CameraSession::get`] and [`CameraSession::set`] read and write the properties
+//! declared in [`props`], with their value type known at compile time and checked
+//! against the type reported by the camera; [`get`] reads them from any
+//! [`EdsPropertyHolder`], such as a live view image. [`get_property`] and [`set_property`]
+//! work on any property with a [`PropertyValue`]. [`CameraSession::watch`] keeps
+//! a property up to date from the property events of the camera.
 //!
-//! For example, `eds_delete_directory_item` expects an `EdsDirectoryItemRef`, which
-//! is actually a type alias for `EdsBaseRef`. However, passing an `EdsCameraRef` (also
-//! an alias for `EdsBaseRef`) by mistake will compile just fine, since both are the
-//! same underlying type.
+//! Exposure settings convert to and from their physical value, e.g.
+//! [`EdsTv::seconds`] and [`EdsTv::from_seconds`], are ordered by it, and can be
+//! moved by thirds or halves of a stop among the allowed values with
+//! [`Stops::step`].
 //!
-//! **It is therefore critical to ensure that the correct type of pointer is passed
-//! to each function**, as mismatches can lead to SDK-level errors such as
-//! "invalid pointer" or undefined behavior.
+//! ## Errors
 //!
-//! Always consult the SDK documentation and double-check that the pointer being passed
-//! is of the exact expected kind.
+//! Failing calls return an [`Error`], holding the [`EdsError`] code returned by the
+//! SDK and the call that returned it. It implements `std::error::Error`, and can
+//! be classified with methods such as [`Error::is_device_busy`] or
+//! [`Error::is_retryable`].
+//!
+//! ## Events
+//!
+//! The object, property and state events of a camera are delivered as
+//! [`CameraEvent`]s to every receiver returned by [`CameraSession::events`], and
+//! to the closures registered with [`CameraSession::set_object_handler`] and
+//! its siblings, while `eds_get_event` is being called. [`Sdk::with_event_pump`]
+//! starts a background pump doing so for the lifetime of the SDK.
+//!
+//! ## Shooting
+//!
+//! [`CameraSession::capture`] releases the shutter and resolves to the files
+//! transferred to the host for that shot, and [`CameraSession::bulb_exposure`]
+//! does the same for a timed exposure in bulb mode.
+//! [`CameraSession::run_intervalometer`] starts a task shooting frames at a
+//! fixed interval, e.g. for a timelapse, and streams each frame, how late it was
+//! shot and the statistics of the run. As the task holds on to the session, it
+//! is called on a session shared in an [`Arc`].
+//! [`CameraSession::bracket`] captures a frame per exposure offset by stepping
+//! Tv, Av or ISO, then restores the original setting.
 
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2025 Antoine Lanco
 use std::slice;
 include!("sdk.rs");
 
+mod bracket;
+mod bulb;
+mod cache;
+mod capture;
+mod error;
+mod event;
+mod exposure;
+mod intervalometer;
+mod picture_style;
+mod property;
+pub mod props;
+mod pump;
+mod quality;
+mod white_balance;
+pub use bracket::*;
+use cache::PropertyCache;
+pub use capture::*;
+pub use error::*;
+pub use event::*;
+pub use exposure::*;
+pub use intervalometer::*;
+pub use picture_style::*;
+pub use property::*;
+use pump::{EventPump, with_event_lock};
+pub use pump::{EventPumpConfig, EventPumpMode};
+pub use quality::*;
+pub use white_balance::*;
+
 fn type_check<T: Any>(t: &T, data_t: &EdsDataType) -> bool {
     use EdsDataType::*;
     let ty = t.type_id();
@@ -55,183 +131,392 @@ fn type_check<T: Any>(t: &T, data_t: &EdsDataType) -> bool {
         UInt64 => ty.eq(&TypeId::of::<EdsUInt64>()),
         Float => ty.eq(&TypeId::of::<EdsFloat>()),
         Double => ty.eq(&TypeId::of::<EdsDouble>()),
-        ByteBlock => todo!(),
-        Rational => todo!(),
-        Point => todo!(),
-        Rect => todo!(),
-        Time => todo!(),
-        BoolArray => todo!(),
-        Int8Array => todo!(),
-        Int16Array => todo!(),
-        Int32Array => todo!(),
-        Uint8Array => todo!(),
-        Uint16Array => todo!(),
-        Uint32Array => todo!(),
-        RationalArray => todo!(),
-        FocusInfo => todo!(),
-        PictureStyleDesc => todo!(),
+        ByteBlock => ty.eq(&TypeId::of::<Vec<EdsUInt8>>()),
+        Rational => ty.eq(&TypeId::of::<EdsRational>()),
+        Point => ty.eq(&TypeId::of::<EdsPoint>()),
+        Rect => ty.eq(&TypeId::of::<EdsRect>()),
+        Time => ty.eq(&TypeId::of::<EdsTime>()),
+        BoolArray => ty.eq(&TypeId::of::<Vec<EdsBool>>()),
+        Int8Array => ty.eq(&TypeId::of::<Vec<EdsInt8>>()),
+        Int16Array => ty.eq(&TypeId::of::<Vec<EdsInt16>>()),
+        Int32Array => ty.eq(&TypeId::of::<Vec<EdsInt32>>()),
+        Uint8Array => ty.eq(&TypeId::of::<Vec<EdsUInt8>>()),
+        Uint16Array => ty.eq(&TypeId::of::<Vec<EdsUInt16>>()),
+        Uint32Array => {
+            ty.eq(&TypeId::of::<Vec<EdsUInt32>>()) || ty.eq(&TypeId::of::<[EdsUInt32; 2]>())
+        }
+        RationalArray => ty.eq(&TypeId::of::<Vec<EdsRational>>()),
+        FocusInfo => ty.eq(&TypeId::of::<EdsFocusInfo>()),
+        PictureStyleDesc => ty.eq(&TypeId::of::<EdsPictureStyleDesc>()),
     }
 }
 
-pub async fn get_raw_setting<T>(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
-    prop_id: EdsPropertyID,
-) -> Result<T, EdsError>
+pub async fn get_raw_setting<T, R>(in_ref: &R, prop_id: EdsPropertyID) -> Result<T, Error>
 where
     T: Default,
+    R: EdsPropertyHolder,
 {
-    let (_data_type, size) = eds_get_property_size(in_ref.clone(), prop_id, 0).await?;
+    let (_data_type, size) = eds_get_property_size(in_ref, prop_id, 0).await?;
+    // The data is written to a `T`, so the SDK must not be told it is larger.
+    if size as usize != size_of::<T>() {
+        return Err(EdsError::PropertiesMismatch.into());
+    }
     eds_get_property_data(in_ref, prop_id, 0, size).await
 }
 
-pub async fn get_setting<T, U>(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
-    prop_id: EdsPropertyID,
-) -> Result<U, EdsError>
+pub async fn get_setting<T, U, R>(in_ref: &R, prop_id: EdsPropertyID) -> Result<U, Error>
 where
     U: TryFromPrimitive<Primitive = T>,
     T: Default,
+    R: EdsPropertyHolder,
 {
     let val = get_raw_setting(in_ref, prop_id).await?;
-    U::try_from_primitive(val).map_err(|_| EdsError::PropertiesMismatch)
+    U::try_from_primitive(val).map_err(|_| Error::new(EdsError::PropertiesMismatch))
 }
 
-async fn set_setting<T, U>(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+async fn set_setting<T, U, R>(
+    in_ref: &R,
     in_property_id: EdsPropertyID,
     value: U,
-) -> Result<(), EdsError>
+) -> Result<(), Error>
 where
     U: 'static + Into<T> + TryFromPrimitive<Primitive = T>,
     T: 'static + Debug,
+    R: EdsPropertyHolder,
 {
-    let (data_type, in_property_size) =
-        eds_get_property_size(in_ref.clone(), in_property_id, 0).await?;
+    let (data_type, in_property_size) = eds_get_property_size(in_ref, in_property_id, 0).await?;
     let v: T = value.into();
     #[cfg(debug_assertions)]
     assert!(type_check(&v, &data_type), "{:?} == {:?}", v, data_type);
-    let in_property_data = (&v as *const T) as *const EdsVoid;
-    eds_set_property_data(
-        in_ref,
-        in_property_id,
-        0,
-        in_property_size,
-        in_property_data,
-    )
-    .await
+    // The data is borrowed from `v`, so the SDK must not be told it is larger.
+    if in_property_size as usize != size_of::<T>() {
+        return Err(EdsError::PropertiesMismatch.into());
+    }
+    let in_property_data =
+        unsafe { slice::from_raw_parts(&v as *const T as *const u8, size_of::<T>()) };
+    eds_set_property_data(in_ref, in_property_id, 0, in_property_data).await
 }
 
-pub async fn set_save_to(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
-    save_to: EdsSaveTo,
-) -> Result<(), EdsError> {
-    set_setting(in_ref, EdsPropertyID::SaveTo, save_to).await
+impl Camera {
+    /// Opens a session on the camera with `EdsOpenSession`. Fails with
+    /// `EdsError::SessionAlreadyOpen` while another session on the same camera
+    /// is alive.
+    pub async fn open_session(&self) -> Result<CameraSession, Error> {
+        self.1.claim_session(self.as_raw())?;
+        if let Err(err) = eds_open_session(self).await {
+            self.1.release_session(self.as_raw());
+            return Err(err);
+        }
+        let session = CameraSession {
+            camera: self.clone(),
+            open: true,
+            events: EventDispatcher::new(self),
+            abandoned_groups: Default::default(),
+        };
+        session.events.install()?;
+        Ok(session)
+    }
 }
 
-pub async fn set_evf_depth_of_field_preview(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
-    evf: EdsEvfDepthOfFieldPreview,
-) -> Result<(), EdsError> {
-    set_setting(in_ref, EdsPropertyID::EvfDepthOfFieldPreview, evf).await
+/// Open session on a [`Camera`].
+///
+/// Properties, commands and event handlers of a camera are only reachable
+/// through its session. The session is closed with `EdsCloseSession` when
+/// dropped; use [`CameraSession::close`] to get the result of that call.
+#[derive(Debug)]
+pub struct CameraSession {
+    camera: Camera,
+    open: bool,
+    events: Arc<EventDispatcher>,
+    /// Groups of the shots whose files were not all received, see
+    /// [`CameraSession::capture_with`].
+    abandoned_groups: std::sync::Mutex<std::collections::HashSet<EdsUInt32>>,
 }
 
-pub async fn set_output_device(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    output_device: EdsEvfOutputDevice,
-) -> Result<(), EdsError> {
-    set_setting(camera_ref, EdsPropertyID::EvfOutputDevice, output_device).await
+impl EdsObject for CameraSession {
+    fn as_base_ref(&self) -> EdsBaseRef {
+        self.camera.as_base_ref()
+    }
 }
 
-pub async fn set_evf_mode(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    mode: EdsEvfMode,
-) -> Result<(), EdsError> {
-    set_setting(camera_ref.clone(), EdsPropertyID::EvfMode, mode).await?;
-    set_evf_depth_of_field_preview(camera_ref, EdsEvfDepthOfFieldPreview::Off).await
-}
+impl EdsPropertyHolder for CameraSession {}
 
-pub async fn get_white_balance(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-) -> Result<EdsWhiteBalance, EdsError> {
-    get_setting(camera_ref, EdsPropertyID::WhiteBalance).await
-}
-pub async fn get_quality(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-) -> Result<EdsImageQuality, EdsError> {
-    get_setting(camera_ref, EdsPropertyID::ImageQuality).await
-}
-pub async fn get_iso(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<EdsISO, EdsError> {
-    get_setting(camera_ref, EdsPropertyID::ISOSpeed).await
-}
-pub async fn get_tv(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<EdsTv, EdsError> {
-    get_setting(camera_ref, EdsPropertyID::Tv).await
-}
-pub async fn get_av(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<EdsAv, EdsError> {
-    get_setting(camera_ref, EdsPropertyID::Av).await
-}
-
-pub async fn get_all<T>(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    prop_id: EdsPropertyID,
-) -> Result<Vec<T>, EdsError>
-where
-    T: TryFrom<i32>,
-{
-    let desc = eds_get_property_desc(camera_ref, prop_id).await?;
-    let mut res = vec![];
-    for i in 0..desc.num_elements {
-        let v = desc.prop_desc[i as usize];
-        if let Ok(va) = v.try_into() {
-            res.push(va);
+impl Drop for CameraSession {
+    fn drop(&mut self) {
+        if self.open {
+            let _ = self.events.uninstall();
+            let _ = check_call!(EdsCloseSession(self.camera.as_raw()));
+            self.camera.1.release_session(self.as_raw());
         }
     }
-    Ok(res)
 }
 
-pub async fn get_all_quality(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-) -> Result<Vec<EdsImageQuality>, EdsError> {
-    get_all(camera_ref, EdsPropertyID::ImageQuality).await
-}
+impl CameraSession {
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
 
-pub async fn get_all_white_balance(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-) -> Result<Vec<EdsWhiteBalance>, EdsError> {
-    get_all(camera_ref, EdsPropertyID::WhiteBalance).await
-}
+    /// Closes the session, reporting the error returned by `EdsCloseSession`.
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.open = false;
+        let uninstalled = self.events.uninstall();
+        let closed = eds_close_session(&self.camera).await;
+        self.camera.1.release_session(self.as_raw());
+        closed.and(uninstalled)
+    }
 
-pub async fn get_all_iso(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<Vec<EdsISO>, EdsError> {
-    get_all(camera_ref, EdsPropertyID::ISOSpeed).await
-}
+    fn as_raw(&self) -> EdsCameraRef {
+        self.camera.as_raw()
+    }
 
-pub async fn get_all_av(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<Vec<EdsAv>, EdsError> {
-    get_all(camera_ref, EdsPropertyID::Av).await
-}
+    pub async fn send_command<T>(&self, command: EdsCameraCommand, param: T) -> Result<(), Error>
+    where
+        T: Into<i32>,
+    {
+        eds_send_command(self, command, param).await
+    }
 
-pub async fn get_all_tv(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<Vec<EdsTv>, EdsError> {
-    get_all(camera_ref, EdsPropertyID::Tv).await
-}
+    pub async fn send_status_command(
+        &self,
+        command: EdsCameraStatusCommand,
+        param: EdsInt32,
+    ) -> Result<(), Error> {
+        eds_send_status_command(self, command, param).await
+    }
 
-pub async fn set_quality(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    value: EdsImageQuality,
-) -> Result<(), EdsError> {
-    set_setting(camera_ref, EdsPropertyID::ImageQuality, value).await
-}
-pub async fn set_white_balance(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    value: EdsWhiteBalance,
-) -> Result<(), EdsError> {
-    set_setting(camera_ref, EdsPropertyID::WhiteBalance, value).await
-}
-pub async fn set_iso(camera_ref: EdsRefWrapper<EdsBaseRef>, value: EdsISO) -> Result<(), EdsError> {
-    set_setting(camera_ref, EdsPropertyID::ISOSpeed, value).await
-}
-pub async fn set_av(camera_ref: EdsRefWrapper<EdsBaseRef>, value: EdsAv) -> Result<(), EdsError> {
-    set_setting(camera_ref, EdsPropertyID::Av, value).await
+    pub async fn set_capacity(&self, capacity: EdsCapacity) -> Result<(), Error> {
+        eds_set_capacity(self, capacity).await
+    }
+
+    pub async fn set_save_to(&self, save_to: EdsSaveTo) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::SaveTo, save_to).await
+    }
+
+    pub async fn set_evf_depth_of_field_preview(
+        &self,
+        evf: EdsEvfDepthOfFieldPreview,
+    ) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::EvfDepthOfFieldPreview, evf).await
+    }
+
+    pub async fn set_output_device(&self, output_device: EdsEvfOutputDevice) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::EvfOutputDevice, output_device).await
+    }
+
+    pub async fn set_evf_mode(&self, mode: EdsEvfMode) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::EvfMode, mode).await?;
+        self.set_evf_depth_of_field_preview(EdsEvfDepthOfFieldPreview::Off)
+            .await
+    }
+
+    pub async fn get_white_balance(&self) -> Result<EdsWhiteBalance, Error> {
+        get_setting(self, EdsPropertyID::WhiteBalance).await
+    }
+    pub async fn get_quality(&self) -> Result<EdsImageQuality, Error> {
+        get_setting(self, EdsPropertyID::ImageQuality).await
+    }
+    pub async fn get_iso(&self) -> Result<EdsISO, Error> {
+        get_setting(self, EdsPropertyID::ISOSpeed).await
+    }
+    pub async fn get_tv(&self) -> Result<EdsTv, Error> {
+        get_setting(self, EdsPropertyID::Tv).await
+    }
+    pub async fn get_av(&self) -> Result<EdsAv, Error> {
+        get_setting(self, EdsPropertyID::Av).await
+    }
+    pub async fn get_exposure_compensation(&self) -> Result<EdsExposureCompensation, Error> {
+        get_setting(self, EdsPropertyID::ExposureCompensation).await
+    }
+    pub async fn get_drive_mode(&self) -> Result<EdsDriveMode, Error> {
+        get_setting(self, EdsPropertyID::DriveMode).await
+    }
+    pub async fn get_metering_mode(&self) -> Result<EdsMeteringMode, Error> {
+        get_setting(self, EdsPropertyID::MeteringMode).await
+    }
+    pub async fn get_af_mode(&self) -> Result<EdsAFMode, Error> {
+        get_setting(self, EdsPropertyID::AFMode).await
+    }
+    pub async fn get_red_eye(&self) -> Result<EdsRedEye, Error> {
+        get_setting(self, EdsPropertyID::RedEye).await
+    }
+    pub async fn get_noise_reduction(&self) -> Result<EdsNoiseReduction, Error> {
+        get_setting(self, EdsPropertyID::NoiseReduction).await
+    }
+    pub async fn get_flash_mode(&self) -> Result<EdsFlashMode, Error> {
+        get_setting(self, EdsPropertyID::FlashMode).await
+    }
+    pub async fn get_orientation(&self) -> Result<EdsOrientation, Error> {
+        get_setting(self, EdsPropertyID::Orientation).await
+    }
+
+    pub async fn get_all_quality(&self) -> Result<Vec<EdsImageQuality>, Error> {
+        Ok(self.describe::<props::ImageQuality>().await?.values)
+    }
+
+    pub async fn get_all_white_balance(&self) -> Result<Vec<EdsWhiteBalance>, Error> {
+        Ok(self.describe::<props::WhiteBalance>().await?.values)
+    }
+
+    pub async fn get_all_iso(&self) -> Result<Vec<EdsISO>, Error> {
+        Ok(self.describe::<props::ISOSpeed>().await?.values)
+    }
+
+    pub async fn get_all_av(&self) -> Result<Vec<EdsAv>, Error> {
+        Ok(self.describe::<props::Av>().await?.values)
+    }
+
+    pub async fn get_all_tv(&self) -> Result<Vec<EdsTv>, Error> {
+        Ok(self.describe::<props::Tv>().await?.values)
+    }
+
+    pub async fn get_all_exposure_compensation(
+        &self,
+    ) -> Result<Vec<EdsExposureCompensation>, Error> {
+        Ok(self.describe::<props::ExposureCompensation>().await?.values)
+    }
+
+    pub async fn get_all_drive_mode(&self) -> Result<Vec<EdsDriveMode>, Error> {
+        Ok(self.describe::<props::DriveMode>().await?.values)
+    }
+
+    pub async fn get_all_metering_mode(&self) -> Result<Vec<EdsMeteringMode>, Error> {
+        Ok(self.describe::<props::MeteringMode>().await?.values)
+    }
+
+    pub async fn get_all_af_mode(&self) -> Result<Vec<EdsAFMode>, Error> {
+        Ok(self.describe::<props::AFMode>().await?.values)
+    }
+
+    pub async fn get_all_red_eye(&self) -> Result<Vec<EdsRedEye>, Error> {
+        Ok(self.describe::<props::RedEye>().await?.values)
+    }
+
+    pub async fn get_all_noise_reduction(&self) -> Result<Vec<EdsNoiseReduction>, Error> {
+        Ok(self.describe::<props::NoiseReduction>().await?.values)
+    }
+
+    pub async fn get_all_flash_mode(&self) -> Result<Vec<EdsFlashMode>, Error> {
+        Ok(self.describe::<props::FlashMode>().await?.values)
+    }
+
+    pub async fn get_all_orientation(&self) -> Result<Vec<EdsOrientation>, Error> {
+        Ok(self.describe::<props::Orientation>().await?.values)
+    }
+
+    pub async fn set_quality(&self, value: EdsImageQuality) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::ImageQuality, value).await
+    }
+    pub async fn set_white_balance(&self, value: EdsWhiteBalance) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::WhiteBalance, value).await
+    }
+    pub async fn set_iso(&self, value: EdsISO) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::ISOSpeed, value).await
+    }
+    pub async fn set_av(&self, value: EdsAv) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::Av, value).await
+    }
+    pub async fn set_tv(&self, value: EdsTv) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::Tv, value).await
+    }
+    pub async fn set_exposure_compensation(
+        &self,
+        value: EdsExposureCompensation,
+    ) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::ExposureCompensation, value).await
+    }
+    pub async fn set_drive_mode(&self, value: EdsDriveMode) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::DriveMode, value).await
+    }
+    pub async fn set_metering_mode(&self, value: EdsMeteringMode) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::MeteringMode, value).await
+    }
+    pub async fn set_af_mode(&self, value: EdsAFMode) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::AFMode, value).await
+    }
+    pub async fn set_red_eye(&self, value: EdsRedEye) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::RedEye, value).await
+    }
+    pub async fn set_noise_reduction(&self, value: EdsNoiseReduction) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::NoiseReduction, value).await
+    }
+    pub async fn set_flash_mode(&self, value: EdsFlashMode) -> Result<(), Error> {
+        set_setting(self, EdsPropertyID::FlashMode, value).await
+    }
+
+    pub async fn set_mode(&self, mode: Mode) -> Result<(), Error> {
+        let (evf_mode, output_device) = match mode {
+            Mode::Video => (EdsEvfMode::Enable, EdsEvfOutputDevice::PC),
+            Mode::Photo => (EdsEvfMode::Disable, EdsEvfOutputDevice::Z),
+        };
+        self.set_evf_mode(evf_mode).await?;
+        self.set_output_device(output_device).await
+    }
+
+    pub async fn get_product_name(&self) -> Result<String, Error> {
+        self.get::<props::ProductName>().await
+    }
+    pub async fn get_owner_name(&self) -> Result<String, Error> {
+        self.get::<props::OwnerName>().await
+    }
+    pub async fn get_maker_name(&self) -> Result<String, Error> {
+        self.get::<props::MakerName>().await
+    }
+    pub async fn get_firmware_version(&self) -> Result<String, Error> {
+        self.get::<props::FirmwareVersion>().await
+    }
+    /// Serial number of the body.
+    pub async fn get_body_id(&self) -> Result<String, Error> {
+        self.get::<props::BodyIDEx>().await
+    }
+    pub async fn get_lens_name(&self) -> Result<String, Error> {
+        self.get::<props::LensName>().await
+    }
+    pub async fn get_artist(&self) -> Result<String, Error> {
+        self.get::<props::Artist>().await
+    }
+    pub async fn get_copyright(&self) -> Result<String, Error> {
+        self.get::<props::Copyright>().await
+    }
+    pub async fn get_current_storage(&self) -> Result<String, Error> {
+        self.get::<props::CurrentStorage>().await
+    }
+    pub async fn get_current_folder(&self) -> Result<String, Error> {
+        self.get::<props::CurrentFolder>().await
+    }
+    pub async fn get_hd_directory_structure(&self) -> Result<String, Error> {
+        self.get::<props::HDDirectoryStructure>().await
+    }
+
+    pub async fn set_owner_name(&self, value: &str) -> Result<(), Error> {
+        check_eds_string(value, EDS_MAX_OWNER_NAME)?;
+        self.set::<props::OwnerName>(value.to_owned()).await
+    }
+
+    pub async fn set_artist(&self, value: &str) -> Result<(), Error> {
+        check_eds_string(value, EDS_MAX_ARTIST)?;
+        self.set::<props::Artist>(value.to_owned()).await
+    }
+
+    pub async fn set_copyright(&self, value: &str) -> Result<(), Error> {
+        check_eds_string(value, EDS_MAX_COPYRIGHT)?;
+        self.set::<props::Copyright>(value.to_owned()).await
+    }
 }
-pub async fn set_tv(camera_ref: EdsRefWrapper<EdsBaseRef>, value: EdsTv) -> Result<(), EdsError> {
-    set_setting(camera_ref, EdsPropertyID::Tv, value).await
+
+/// Size of the owner name buffer of the camera, terminating NUL included.
+pub const EDS_MAX_OWNER_NAME: usize = 32;
+/// Size of the artist buffer of the camera, terminating NUL included.
+pub const EDS_MAX_ARTIST: usize = 64;
+/// Size of the copyright buffer of the camera, terminating NUL included.
+pub const EDS_MAX_COPYRIGHT: usize = 64;
+
+/// Checks that `value` fits in an `EdsChar` buffer of `max` bytes, and has no
+/// NUL that would truncate it.
+fn check_eds_string(value: &str, max: usize) -> Result<(), Error> {
+    if value.len() >= max || value.contains('\0') {
+        return Err(EdsError::InvalidParameter.into());
+    }
+    Ok(())
 }
 
 pub enum Mode {
@@ -239,38 +524,14 @@ pub enum Mode {
     Photo,
 }
 
-pub async fn set_mode(camera_ref: EdsRefWrapper<EdsBaseRef>, mode: Mode) -> Result<(), EdsError> {
-    let (evf_mode, output_device) = match mode {
-        Mode::Video => (EdsEvfMode::Enable, EdsEvfOutputDevice::PC),
-        Mode::Photo => (EdsEvfMode::Disable, EdsEvfOutputDevice::Z),
-    };
-    set_evf_mode(camera_ref.clone(), evf_mode).await?;
-    set_output_device(camera_ref, output_device).await
-}
-
-pub async fn data_from_out_stream(
-    out_stream: EdsRefWrapper<EdsStreamRef>,
-) -> Result<Vec<u8>, EdsError> {
-    let wrapped_live_ptr = eds_get_pointer(out_stream.clone()).await?;
+pub async fn data_from_out_stream(out_stream: &Stream) -> Result<Vec<u8>, Error> {
+    let wrapped_live_ptr = eds_get_pointer(out_stream).await?;
     let live_ptr_len = eds_get_length(out_stream).await?;
     let live_ptr = wrapped_live_ptr.0.lock().await.0.0 as *const u8;
     let data = unsafe { slice::from_raw_parts(live_ptr, live_ptr_len as usize).to_vec() };
     Ok(data)
 }
 
-#[repr(C)]
-#[derive(Debug, Default, Clone, Copy)]
-pub struct StateContext {
-    pub job_status: EdsUInt32,
-}
-#[repr(C)]
-#[derive(Debug, Default, Clone, Copy)]
-pub struct ObjectContext {}
-
-#[repr(C)]
-#[derive(Debug, Default, Clone, Copy)]
-pub struct PropertyContext {}
-
 #[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
 pub struct ProgressContext {}
@@ -278,131 +539,25 @@ pub struct ProgressContext {}
 #[derive(Debug, Default, Clone, Copy)]
 pub struct CameraAddedContext {}
 
-#[macro_export]
-macro_rules! set_object_event_handler {
-    ($camera_ref:ident, $func:ident) => {{
-        use std::sync::Arc;
-        use tokio::sync::Mutex;
-        use $crate::{EdsBaseRef, EdsError, EdsObjectEvent, EdsVoid};
-        let object_context = Arc::new(Mutex::new(ObjectContext::default()));
-        {
-            extern "C" fn wrapper(
-                in_event: EdsObjectEvent,
-                in_ref: EdsBaseRef,
-                context: *mut EdsVoid,
-            ) -> EdsError {
-                let arc = unsafe { Arc::from_raw(context as *const Mutex<ObjectContext>) };
-                let result = $func(in_event, EdsRefWrapper::new(in_ref), arc.clone());
-                std::mem::forget(arc);
-                match result {
-                    Ok(()) => EdsError::Ok,
-                    Err(err) => err,
-                }
-            }
-            let object_handler: EdsObjectEventHandler =
-                Some(wrapper as extern "C" fn(_, _, _) -> _);
-            let object_context = Arc::into_raw(object_context.clone()) as *mut EdsVoid;
-            eds_set_object_event_handler(
-                $camera_ref.clone(),
-                EdsObjectEvent::All,
-                object_handler,
-                object_context,
-            )
-            .await?;
-        }
-
-        object_context
-    }};
-}
-#[macro_export]
-macro_rules! set_state_event_handler {
-    ($camera_ref:ident, $func:ident) => {{
-        use std::sync::Arc;
-        use tokio::sync::Mutex;
-        use $crate::{EdsBaseRef, EdsError, EdsStateEvent, EdsVoid};
-        let state_context = Arc::new(Mutex::new(StateContext::default()));
-        {
-            extern "C" fn wrapper(
-                in_event: EdsStateEvent,
-                event_data: EdsUInt32,
-                context: *mut EdsVoid,
-            ) -> EdsError {
-                let arc = unsafe { Arc::from_raw(context as *const Mutex<StateContext>) };
-                let result = $func(in_event, event_data, arc.clone());
-                std::mem::forget(arc);
-                match result {
-                    Ok(()) => EdsError::Ok,
-                    Err(err) => err,
-                }
-            }
-            let state_handler: EdsStateEventHandler = Some(wrapper as extern "C" fn(_, _, _) -> _);
-            let state_context = Arc::into_raw(state_context.clone()) as *mut EdsVoid;
-            eds_set_camera_state_event_handler(
-                $camera_ref.clone(),
-                EdsStateEvent::All,
-                state_handler,
-                state_context,
-            )
-            .await?;
-        };
-        state_context
-    }};
-}
-#[macro_export]
-macro_rules! set_property_event_handler {
-    ($camera_ref:ident, $func:ident) => {{
-        use std::sync::Arc;
-        use tokio::sync::Mutex;
-        use $crate::{EdsBaseRef, EdsError, EdsVoid};
-        let property_context = Arc::new(Mutex::new(PropertyContext {}));
-        {
-            extern "C" fn wrapper(
-                in_event: EdsPropertyEvent,
-                id: EdsPropertyID,
-                event_data: EdsUInt32,
-                context: *mut EdsVoid,
-            ) -> EdsError {
-                let arc = unsafe { Arc::from_raw(context as *const Mutex<PropertyContext>) };
-                let result = $func(in_event, id, event_data, arc.clone());
-                std::mem::forget(arc);
-                match result {
-                    Ok(()) => EdsError::Ok,
-                    Err(err) => err,
-                }
-            }
-            let property_handler: EdsPropertyEventHandler =
-                Some(wrapper as extern "C" fn(_, _, _, _) -> _);
-            let property_context = Arc::into_raw(property_context.clone()) as *mut EdsVoid;
-            eds_set_property_event_handler(
-                $camera_ref.clone(),
-                EdsPropertyEvent::All,
-                property_handler,
-                property_context,
-            )
-            .await?;
-        };
-        property_context
-    }};
-}
 #[macro_export]
 macro_rules! set_progress_callback {
     ($camera_ref:ident, $func:ident) => {{
         use std::sync::Arc;
         use tokio::sync::Mutex;
-        use $crate::{EdsBaseRef, EdsError, EdsVoid};
+        use $crate::{EdsBaseRef, EdsError, EdsErrorCode, EdsVoid};
         let progress_context = Arc::new(Mutex::new(ProgressContext {}));
         {
             extern "C" fn wrapper(
                 percent: EdsUInt32,
                 context: *mut EdsVoid,
                 cancel: *mut EdsBool,
-            ) -> EdsError {
+            ) -> EdsErrorCode {
                 let arc = unsafe { Arc::from_raw(context as *const Mutex<ProgressContext>) };
                 let result = $func(percent, arc.clone(), cancel);
                 std::mem::forget(arc);
                 match result {
-                    Ok(()) => EdsError::Ok,
-                    Err(err) => err,
+                    Ok(()) => EdsError::Ok.into(),
+                    Err(err) => $crate::Error::from(err).raw_code(),
                 }
             }
             let progress_callback: EdsProgressCallback =
@@ -411,7 +566,7 @@ macro_rules! set_progress_callback {
             let progress_context = Arc::into_raw(progress_context.clone()) as *mut EdsVoid;
 
             eds_set_progress_callback(
-                $camera_ref.clone(),
+                &$camera_ref,
                 progress_callback,
                 EdsProgressOption::Periodically,
                 progress_context,
@@ -426,16 +581,16 @@ macro_rules! set_camera_added {
     ($ctx:ident, $func:ident) => {{
         use std::sync::Arc;
         use tokio::sync::Mutex;
-        use $crate::{EdsBaseRef, EdsError, EdsVoid};
+        use $crate::{EdsBaseRef, EdsError, EdsErrorCode, EdsVoid};
 
         {
-            extern "C" fn wrapper(context: *mut EdsVoid) -> EdsError {
+            extern "C" fn wrapper(context: *mut EdsVoid) -> EdsErrorCode {
                 let arc = unsafe { Arc::from_raw(context as *const Mutex<CameraAddedContext>) };
                 let result = $func(arc.clone());
                 std::mem::forget(arc);
                 match result {
-                    Ok(()) => EdsError::Ok,
-                    Err(err) => err,
+                    Ok(()) => EdsError::Ok.into(),
+                    Err(err) => $crate::Error::from(err).raw_code(),
                 }
             }
             let camera_added_callback: EdsCameraAddedHandler =
@@ -2,7 +2,7 @@
 // Copyright (c) 2025 Antoine Lanco
 
 use macro_lib::{IntoI32, TryFromI32};
-use num_enum::{IntoPrimitive, TryFromPrimitive};
+use num_enum::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
 use std::{fmt::Debug, os::raw::c_void};
 
 #[repr(transparent)]
@@ -42,18 +42,57 @@ impl EdsBaseRef {
 }
 // pub type EdsBaseRef = *mut __EdsObject;
 
-pub type EdsCameraListRef = EdsBaseRef;
-pub type EdsCameraRef = EdsBaseRef;
-pub type EdsVolumeRef = EdsBaseRef;
-pub type EdsDirectoryItemRef = EdsBaseRef;
-pub type EdsStreamRef = EdsBaseRef;
-pub type EdsImageRef = EdsStreamRef;
-pub type EdsEvfImageRef = EdsBaseRef;
+/// Declares a reference to one specific kind of SDK object.
+///
+/// The C headers alias every reference to `EdsBaseRef`; making each kind its
+/// own type lets the compiler reject a camera passed where a directory item
+/// is expected. Widening to `EdsBaseRef` is always allowed, narrowing is
+/// `unsafe` because the SDK gives no way to check the kind of an object.
+macro_rules! eds_ref {
+    ($($(#[$attr:meta])* $name:ident;)*) => {$(
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Debug, Copy, Clone)]
+        pub struct $name(EdsBaseRef);
+
+        impl $name {
+            #[allow(dead_code)]
+            fn new() -> $name {
+                $name(EdsBaseRef::new())
+            }
+
+            /// # Safety
+            /// `in_ref` must be null or reference an SDK object of this kind.
+            pub unsafe fn from_base(in_ref: EdsBaseRef) -> $name {
+                $name(in_ref)
+            }
+
+            pub fn is_null(&self) -> bool {
+                self.0.0.is_null()
+            }
+        }
+
+        impl From<$name> for EdsBaseRef {
+            fn from(in_ref: $name) -> EdsBaseRef {
+                in_ref.0
+            }
+        }
+    )*};
+}
+
+eds_ref! {
+    EdsCameraListRef;
+    EdsCameraRef;
+    EdsVolumeRef;
+    EdsDirectoryItemRef;
+    EdsStreamRef;
+    EdsImageRef;
+    EdsEvfImageRef;
+}
 
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromPrimitive, IntoPrimitive)]
 pub enum EdsPropertyID {
-    Unknown = 65535,
     ProductName = 2,
     OwnerName = 4,
     MakerName = 5,
@@ -176,6 +215,9 @@ pub enum EdsPropertyID {
     DcZoom = 1536,
     DcStrobe = 1537,
     LensBarrelStatus = 1541,
+    /// `kEdsPropID_Unknown` (`0xFFFF`), or any ID this crate does not list.
+    #[num_enum(catch_all)]
+    Unknown(u32),
 }
 
 #[repr(u32)]
@@ -207,14 +249,16 @@ pub enum EdsCameraStatusCommand {
     ExitDirectTransfer = 3,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 pub enum EdsPropertyEvent {
     All = 256,
     PropertyChanged = 257,
     PropertyDescChanged = 258,
+    #[num_enum(catch_all)]
+    Unknown(u32),
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 pub enum EdsObjectEvent {
     All = 512,
     VolumeInfoChanged = 513,
@@ -229,9 +273,11 @@ pub enum EdsObjectEvent {
     DirItemCancelTransferDT = 522,
     VolumeAdded = 524,
     VolumeRemoved = 525,
+    #[num_enum(catch_all)]
+    Unknown(u32),
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 pub enum EdsStateEvent {
     All = 768,
     Shutdown = 769,
@@ -243,18 +289,37 @@ pub enum EdsStateEvent {
     AfResult = 777,
     BulbExposureTime = 784,
     PowerZoomInfoChanged = 785,
+    #[num_enum(catch_all)]
+    Unknown(u32),
 }
 
+/// Raw error code, as returned by the SDK.
+///
+/// Besides the error ID, the code holds the component that raised the error and
+/// whether it is specific to that component; see [`EdsError::from_code`].
+pub type EdsErrorCode = EdsUInt32;
+
+pub const EDS_ERR_OK: EdsErrorCode = 0x00000000;
+pub const EDS_ISSPECIFIC_MASK: EdsErrorCode = 0x80000000;
+pub const EDS_COMPONENTID_MASK: EdsErrorCode = 0x7F000000;
+pub const EDS_RESERVED_MASK: EdsErrorCode = 0x00FF0000;
+pub const EDS_ERRORID_MASK: EdsErrorCode = 0x0000FFFF;
+
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
-pub enum EdsError {
-    IsspecificMask = 2147483648,
-    ComponentidMask = 2130706432,
-    ReservedMask = 16711680,
-    ErroridMask = 65535,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
+pub enum EdsComponentId {
+    None = 0,
     CmpIdClientComponentid = 16777216,
     CmpIdLlsdkComponentid = 33554432,
     CmpIdHlsdkComponentid = 50331648,
+    #[num_enum(catch_all)]
+    Unknown(u32),
+}
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
+pub enum EdsError {
     Ok = 0,
     Unimplemented = 1,
     InternalError = 2,
@@ -385,10 +450,28 @@ pub enum EdsError {
     TakePictureMovieModeNg = 36110,
     TakePictureRetructedLensNg = 36111,
     LastGenericErrorPlusOne = 245,
+    #[num_enum(catch_all)]
+    Unknown(u32),
+}
+
+impl EdsError {
+    /// Decodes the error ID of a raw SDK code, ignoring the component and
+    /// specific bits. Unlisted IDs are kept as `Unknown`, as are the codes other
+    /// than `EDS_ERR_OK` with an error ID of zero, kept whole.
+    pub fn from_code(code: EdsErrorCode) -> Self {
+        if code == EDS_ERR_OK {
+            return Self::Ok;
+        }
+        match Self::from(code & EDS_ERRORID_MASK) {
+            Self::Ok => Self::Unknown(code),
+            error => error,
+        }
+    }
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 pub enum EdsDataType {
+    /// Also decoded from any type unknown to this crate.
     #[default]
     Unknown = 0,
     Bool = 1,
@@ -467,7 +550,7 @@ pub enum EdsSeekOrigin {
     End = 2,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, TryFromPrimitive)]
 pub enum EdsAccess {
     #[default]
     Read = 0,
@@ -485,7 +568,7 @@ pub enum EdsFileCreateDisposition {
     TruncateExsisting = 4,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 pub enum EdsImageType {
     Unknown = 0,
     Jpeg = 1,
@@ -495,7 +578,7 @@ pub enum EdsImageType {
     HEIF = 8,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 pub enum EdsImageSize {
     Large = 0,
     Middle = 1,
@@ -508,7 +591,7 @@ pub enum EdsImageSize {
     Unknown = 0xffffffff,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 pub enum EdsCompressQuality {
     Normal = 2,
     Fine = 3,
@@ -517,7 +600,7 @@ pub enum EdsCompressQuality {
     Unknown = 0xffffffff,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, TryFromI32)]
 pub enum EdsImageQuality {
     LJ = 1113871,
     MJ = 17891087,
@@ -674,7 +757,7 @@ pub enum EdsImageSource {
     RAWFullView = 4,
 }
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
 pub enum EdsAv {
     Av1 = 0x08,
     Av1_1 = 0x0B,
@@ -731,7 +814,7 @@ pub enum EdsAv {
     Av91 = 0x70,
 }
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
 pub enum EdsTv {
     TvBulb = 0x0C,
     Tv30s = 0x10,
@@ -825,7 +908,7 @@ pub enum EdsBatteryQuality {
 }
 
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
 pub enum EdsISO {
     Auto = 0,
     ISO6 = 0x00000028,
@@ -868,6 +951,155 @@ pub enum EdsISO {
     ISO819200 = 0x000000b0,
 }
 
+/// Exposure compensation, in eighths of a stop stored as a signed byte.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsExposureCompensation {
+    Minus5 = 0xD8,
+    Minus4_2_3 = 0xDB,
+    Minus4_1_2 = 0xDC,
+    Minus4_1_3 = 0xDD,
+    Minus4 = 0xE0,
+    Minus3_2_3 = 0xE3,
+    Minus3_1_2 = 0xE4,
+    Minus3_1_3 = 0xE5,
+    Minus3 = 0xE8,
+    Minus2_2_3 = 0xEB,
+    Minus2_1_2 = 0xEC,
+    Minus2_1_3 = 0xED,
+    Minus2 = 0xF0,
+    Minus1_2_3 = 0xF3,
+    Minus1_1_2 = 0xF4,
+    Minus1_1_3 = 0xF5,
+    Minus1 = 0xF8,
+    Minus2_3 = 0xFB,
+    Minus1_2 = 0xFC,
+    Minus1_3 = 0xFD,
+    Zero = 0x00,
+    Plus1_3 = 0x03,
+    Plus1_2 = 0x04,
+    Plus2_3 = 0x05,
+    Plus1 = 0x08,
+    Plus1_1_3 = 0x0B,
+    Plus1_1_2 = 0x0C,
+    Plus1_2_3 = 0x0D,
+    Plus2 = 0x10,
+    Plus2_1_3 = 0x13,
+    Plus2_1_2 = 0x14,
+    Plus2_2_3 = 0x15,
+    Plus3 = 0x18,
+    Plus3_1_3 = 0x1B,
+    Plus3_1_2 = 0x1C,
+    Plus3_2_3 = 0x1D,
+    Plus4 = 0x20,
+    Plus4_1_3 = 0x23,
+    Plus4_1_2 = 0x24,
+    Plus4_2_3 = 0x25,
+    Plus5 = 0x28,
+}
+
+/// Drive mode, see [`EdsPropertyID::DriveMode`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsDriveMode {
+    SingleShooting = 0x00,
+    ContinuousShooting = 0x01,
+    Video = 0x02,
+    HighSpeedContinuous = 0x04,
+    LowSpeedContinuous = 0x05,
+    SingleSilent = 0x06,
+    SelfTimerContinuous = 0x07,
+    SelfTimer10s = 0x10,
+    SelfTimer2s = 0x11,
+    SuperHighSpeed14fps = 0x12,
+    SilentSingle = 0x13,
+    SilentContinuous = 0x14,
+    SilentHighSpeedContinuous = 0x15,
+    SilentLowSpeedContinuous = 0x16,
+    Unknown = 0xffffffff,
+}
+
+/// Metering mode, see [`EdsPropertyID::MeteringMode`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsMeteringMode {
+    Spot = 1,
+    Evaluative = 3,
+    Partial = 4,
+    CenterWeightedAveraging = 5,
+    Unknown = 0xffffffff,
+}
+
+/// Focus mode, see [`EdsPropertyID::AFMode`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsAFMode {
+    OneShot = 0,
+    AIServo = 1,
+    AIFocus = 2,
+    Manual = 3,
+    Unknown = 0xffffffff,
+}
+
+/// Flash fired for an image, first element of [`EdsPropertyID::FlashMode`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsFlashType {
+    None = 0,
+    Internal = 1,
+    ExternalETTL = 2,
+    ExternalATTL = 3,
+    Unknown = 0xffffffff,
+}
+
+/// Flash synchronization, second element of [`EdsPropertyID::FlashMode`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsFlashSyncTiming {
+    FirstCurtain = 0,
+    SecondCurtain = 1,
+    Unknown = 0xffffffff,
+}
+
+/// Value of [`EdsPropertyID::FlashMode`], an array of two `EdsUInt32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdsFlashMode {
+    pub flash_type: EdsFlashType,
+    pub sync_timing: EdsFlashSyncTiming,
+}
+
+/// Red-eye reduction, see [`EdsPropertyID::RedEye`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsRedEye {
+    Off = 0,
+    On = 1,
+    Unknown = 0xffffffff,
+}
+
+/// Long exposure noise reduction, see [`EdsPropertyID::NoiseReduction`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsNoiseReduction {
+    Off = 0,
+    On1 = 1,
+    On2 = 2,
+    On = 3,
+    Auto = 4,
+    Unknown = 0xffffffff,
+}
+
+/// Image orientation, as the Exif tag, see [`EdsPropertyID::Orientation`].
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, TryFromI32)]
+pub enum EdsOrientation {
+    Deg0 = 1,
+    Deg180 = 3,
+    Deg90 = 6,
+    Deg270 = 8,
+    Unknown = 0xffffffff,
+}
+
 #[repr(u32)]
 #[derive(Debug, Clone, Copy)]
 pub enum EdsTargetImageType {
@@ -968,7 +1200,7 @@ pub enum EdsColorSpace {
     Unknown = 0xffffffff,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, TryFromI32)]
 pub enum EdsPictureStyle {
     Standard = 129,
     Portrait = 130,
@@ -985,6 +1217,26 @@ pub enum EdsPictureStyle {
     PC2 = 66,
     PC3 = 67,
 }
+/// Filter effect of the monochrome picture style.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+pub enum EdsFilterEffect {
+    None = 0,
+    Yellow = 1,
+    Orange = 2,
+    Red = 3,
+    Green = 4,
+}
+/// Toning effect of the monochrome picture style.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+pub enum EdsToningEffect {
+    None = 0,
+    Sepia = 1,
+    Blue = 2,
+    Purple = 3,
+    Green = 4,
+}
 #[repr(u32)]
 #[derive(Debug, Clone, Copy)]
 pub enum EdsTransferOption {
@@ -1056,7 +1308,7 @@ pub enum EdsAEMode {
     Unknown = 0xffffffff,
 }
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 pub enum EdsBracket {
     AEB = 1,
     ISOB = 2,
@@ -1161,46 +1413,56 @@ pub enum EdsMirrorUpSetting {
     On = 1,
 }
 
+/// Size of the `EdsChar` buffers of the SDK structures.
+pub const EDS_MAX_NAME: usize = 256;
+
+/// Reads a NUL-terminated `EdsChar` buffer. Bytes that are not valid UTF-8 are
+/// replaced with `U+FFFD`.
+pub fn eds_string(chars: &[EdsChar]) -> String {
+    let end = chars.iter().position(|c| *c == 0).unwrap_or(chars.len());
+    String::from_utf8_lossy(&chars[..end]).into_owned()
+}
+
 #[doc = "Definition of base Structures"]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct TagEdsPoint {
-    x: EdsInt32,
-    y: EdsInt32,
+    pub x: EdsInt32,
+    pub y: EdsInt32,
 }
 #[doc = "Definition of base Structures"]
 pub type EdsPoint = TagEdsPoint;
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct TagEdsSize {
-    width: EdsInt32,
-    height: EdsInt32,
+    pub width: EdsInt32,
+    pub height: EdsInt32,
 }
 pub type EdsSize = TagEdsSize;
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct TagEdsRect {
-    point: EdsPoint,
-    size: EdsSize,
+    pub point: EdsPoint,
+    pub size: EdsSize,
 }
 pub type EdsRect = TagEdsRect;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct TagEdsRational {
-    numerator: EdsInt32,
-    denominator: EdsUInt32,
+    pub numerator: EdsInt32,
+    pub denominator: EdsUInt32,
 }
 pub type EdsRational = TagEdsRational;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct TagEdsTime {
-    year: EdsUInt32,
-    month: EdsUInt32,
-    day: EdsUInt32,
-    hour: EdsUInt32,
-    minute: EdsUInt32,
-    second: EdsUInt32,
-    milliseconds: EdsUInt32,
+    pub year: EdsUInt32,
+    pub month: EdsUInt32,
+    pub day: EdsUInt32,
+    pub hour: EdsUInt32,
+    pub minute: EdsUInt32,
+    pub second: EdsUInt32,
+    pub milliseconds: EdsUInt32,
 }
 pub type EdsTime = TagEdsTime;
 #[repr(C)]
@@ -1230,15 +1492,10 @@ pub struct TagEdsDeviceInfo {
 
 impl TagEdsDeviceInfo {
     pub fn get_sz_port_name(&self) -> String {
-        let s = String::from_utf8(self.sz_port_name.to_vec()).unwrap_or("Unknow".to_string());
-        let cleaned: String = s.chars().filter(|&c| c != '\0').collect();
-        cleaned
+        eds_string(&self.sz_port_name)
     }
     pub fn get_sz_device_description(&self) -> String {
-        let s =
-            String::from_utf8(self.sz_device_description.to_vec()).unwrap_or("Unknow".to_string());
-        let cleaned: String = s.chars().filter(|&c| c != '\0').collect();
-        cleaned
+        eds_string(&self.sz_device_description)
     }
 }
 impl Default for TagEdsDeviceInfo {
@@ -1278,9 +1535,7 @@ pub struct TagEdsVolumeInfo {
 
 impl TagEdsVolumeInfo {
     pub fn get_sz_volume_label(&self) -> String {
-        let s = String::from_utf8(self.sz_volume_label.to_vec()).unwrap_or("Unknow".to_string());
-        let cleaned: String = s.chars().filter(|&c| c != '\0').collect();
-        cleaned
+        eds_string(&self.sz_volume_label)
     }
 }
 
@@ -1324,9 +1579,7 @@ pub struct TagEdsDirectoryItemInfo {
 
 impl TagEdsDirectoryItemInfo {
     pub fn get_sz_file_name(&self) -> String {
-        let s = String::from_utf8(self.sz_file_name.to_vec()).unwrap_or("Unknow".to_string());
-        let cleaned: String = s.chars().filter(|&c| c != '\0').collect();
-        cleaned
+        eds_string(&self.sz_file_name)
     }
 }
 impl Default for TagEdsDirectoryItemInfo {
@@ -1400,7 +1653,7 @@ impl Default for TagEdsPropertyDesc {
 }
 pub type EdsPropertyDesc = TagEdsPropertyDesc;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct TagEdsPictureStyleDesc {
     contrast: EdsInt32,
     sharpness: EdsUInt32,
@@ -1413,22 +1666,22 @@ pub struct TagEdsPictureStyleDesc {
 }
 pub type EdsPictureStyleDesc = TagEdsPictureStyleDesc;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct TagEdsFrameDesc {
-    valid: EdsUInt32,
-    selected: EdsUInt32,
-    just_focus: EdsUInt32,
-    rect: EdsRect,
-    reserved: EdsUInt32,
+    pub valid: EdsUInt32,
+    pub selected: EdsUInt32,
+    pub just_focus: EdsUInt32,
+    pub rect: EdsRect,
+    pub reserved: EdsUInt32,
 }
 pub type EdsFocusPoint = TagEdsFrameDesc;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TagEdsFocusInfo {
-    image_rect: EdsRect,
-    point_number: EdsUInt32,
-    focus_point: [EdsFocusPoint; 1053usize],
-    execute_mode: EdsUInt32,
+    pub image_rect: EdsRect,
+    pub point_number: EdsUInt32,
+    pub focus_point: [EdsFocusPoint; 1053usize],
+    pub execute_mode: EdsUInt32,
 }
 pub type EdsFocusInfo = TagEdsFocusInfo;
 #[repr(C)]
@@ -1494,65 +1747,75 @@ pub struct TagApertureLockSetting {
 }
 pub type ApertureLockSetting = TagApertureLockSetting;
 #[doc = "Callback Functions"]
+#[allow(non_snake_case)]
 pub type EdsProgressCallback = ::std::option::Option<
     unsafe extern "C" fn(
         inPercent: EdsUInt32,
         inContext: *mut EdsVoid,
         outCancel: *mut EdsBool,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsCameraAddedHandler =
-    ::std::option::Option<unsafe extern "C" fn(inContext: *mut EdsVoid) -> EdsError>;
+    ::std::option::Option<unsafe extern "C" fn(inContext: *mut EdsVoid) -> EdsErrorCode>;
+#[allow(non_snake_case)]
 pub type EdsPropertyEventHandler = ::std::option::Option<
     unsafe extern "C" fn(
-        inEvent: EdsPropertyEvent,
-        inPropertyID: EdsPropertyID,
+        inEvent: EdsUInt32,
+        inPropertyID: EdsUInt32,
         inParam: EdsUInt32,
         inContext: *mut EdsVoid,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsObjectEventHandler = ::std::option::Option<
     unsafe extern "C" fn(
-        inEvent: EdsObjectEvent,
+        inEvent: EdsUInt32,
         inRef: EdsBaseRef,
         inContext: *mut EdsVoid,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsStateEventHandler = ::std::option::Option<
     unsafe extern "C" fn(
-        inEvent: EdsStateEvent,
+        inEvent: EdsUInt32,
         inEventData: EdsUInt32,
         inContext: *mut EdsVoid,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsReadStream = ::std::option::Option<
     unsafe extern "C" fn(
         inContext: *mut c_void,
         inReadSize: EdsUInt32,
         outBuffer: *mut EdsVoid,
         outReadSize: *mut EdsUInt32,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsWriteStream = ::std::option::Option<
     unsafe extern "C" fn(
         inContext: *mut c_void,
         inWriteSize: EdsUInt32,
         inBuffer: *const EdsVoid,
         outWrittenSize: *mut EdsUInt32,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsSeekStream = ::std::option::Option<
     unsafe extern "C" fn(
         inContext: *mut c_void,
         inSeekOffset: EdsInt32,
         inSeekOrigin: EdsSeekOrigin,
-    ) -> EdsError,
+    ) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsTellStream = ::std::option::Option<
-    unsafe extern "C" fn(inContext: *mut c_void, outPosition: *mut EdsInt32) -> EdsError,
+    unsafe extern "C" fn(inContext: *mut c_void, outPosition: *mut EdsInt32) -> EdsErrorCode,
 >;
+#[allow(non_snake_case)]
 pub type EdsGetStreamLength = ::std::option::Option<
-    unsafe extern "C" fn(inContext: *mut c_void, outLength: *mut EdsUInt32) -> EdsError,
+    unsafe extern "C" fn(inContext: *mut c_void, outLength: *mut EdsUInt32) -> EdsErrorCode,
 >;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -1566,156 +1829,159 @@ pub struct EdsIStream {
 }
 unsafe extern "C" {
     #[doc = "Basic functions"]
-    fn EdsInitializeSDK() -> EdsError;
+    fn EdsInitializeSDK() -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsTerminateSDK() -> EdsError;
+    fn EdsTerminateSDK() -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Reference-counter operating functions"]
-    fn EdsRetain(inRef: Wrapper<EdsBaseRef>) -> EdsError;
+    fn EdsRetain(inRef: EdsBaseRef) -> EdsUInt32;
 }
 unsafe extern "C" {
-    fn EdsRelease(inRef: EdsBaseRef) -> EdsError;
+    fn EdsRelease(inRef: EdsBaseRef) -> EdsUInt32;
 }
 unsafe extern "C" {
     #[doc = "Item-tree operating functions"]
-    fn EdsGetChildCount(inRef: Wrapper<EdsBaseRef>, outCount: *mut EdsUInt32) -> EdsError;
+    fn EdsGetChildCount(inRef: EdsBaseRef, outCount: *mut EdsUInt32) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsGetChildAtIndex(
-        inRef: Wrapper<EdsBaseRef>,
+        inRef: EdsBaseRef,
         inIndex: EdsInt32,
         outRef: *mut EdsBaseRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsGetParent(inRef: Wrapper<EdsBaseRef>, outParentRef: *mut EdsBaseRef) -> EdsError;
+    fn EdsGetParent(inRef: EdsBaseRef, outParentRef: *mut EdsBaseRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Property operating functions"]
     fn EdsGetPropertySize(
-        inRef: Wrapper<EdsBaseRef>,
-        inPropertyID: EdsPropertyID,
+        inRef: EdsBaseRef,
+        inPropertyID: EdsUInt32,
         inParam: EdsInt32,
-        outDataType: *mut EdsDataType,
+        outDataType: *mut EdsUInt32,
         outSize: *mut EdsUInt32,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsGetPropertyData(
-        inRef: Wrapper<EdsBaseRef>,
-        inPropertyID: EdsPropertyID,
+        inRef: EdsBaseRef,
+        inPropertyID: EdsUInt32,
         inParam: EdsInt32,
         inPropertySize: EdsUInt32,
         outPropertyData: *mut EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetPropertyData(
-        inRef: Wrapper<EdsBaseRef>,
-        inPropertyID: EdsPropertyID,
+        inRef: EdsBaseRef,
+        inPropertyID: EdsUInt32,
         inParam: EdsInt32,
         inPropertySize: EdsUInt32,
         inPropertyData: *const EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsGetPropertyDesc(
-        inRef: Wrapper<EdsBaseRef>,
-        inPropertyID: EdsPropertyID,
+        inRef: EdsBaseRef,
+        inPropertyID: EdsUInt32,
         outPropertyDesc: *mut EdsPropertyDesc,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Device-list and device operating functions"]
-    fn EdsGetCameraList(outCameraListRef: *mut EdsCameraListRef) -> EdsError;
+    fn EdsGetCameraList(outCameraListRef: *mut EdsCameraListRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Camera operating functions"]
     fn EdsGetDeviceInfo(
-        inCameraRef: Wrapper<EdsCameraRef>,
+        inCameraRef: EdsCameraRef,
         outDeviceInfo: *mut EdsDeviceInfo,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsOpenSession(inCameraRef: Wrapper<EdsCameraRef>) -> EdsError;
+    fn EdsOpenSession(inCameraRef: EdsCameraRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsCloseSession(inCameraRef: Wrapper<EdsCameraRef>) -> EdsError;
+    fn EdsCloseSession(inCameraRef: EdsCameraRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSendCommand(
-        inCameraRef: Wrapper<EdsCameraRef>,
+        inCameraRef: EdsCameraRef,
         inCommand: EdsCameraCommand,
         inParam: EdsInt32,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSendStatusCommand(
-        inCameraRef: Wrapper<EdsCameraRef>,
+        inCameraRef: EdsCameraRef,
         inStatusCommand: EdsCameraStatusCommand,
         inParam: EdsInt32,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsSetCapacity(inCameraRef: Wrapper<EdsCameraRef>, inCapacity: EdsCapacity) -> EdsError;
+    fn EdsSetCapacity(inCameraRef: EdsCameraRef, inCapacity: EdsCapacity) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Volume operating functions"]
-    fn EdsGetVolumeInfo(inVolumeRef: EdsVolumeRef, outVolumeInfo: *mut EdsVolumeInfo) -> EdsError;
+    fn EdsGetVolumeInfo(
+        inVolumeRef: EdsVolumeRef,
+        outVolumeInfo: *mut EdsVolumeInfo,
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsFormatVolume(inVolumeRef: EdsVolumeRef) -> EdsError;
+    fn EdsFormatVolume(inVolumeRef: EdsVolumeRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Directory-item operating functions"]
     fn EdsGetDirectoryItemInfo(
-        inDirItemRef: Wrapper<EdsDirectoryItemRef>,
+        inDirItemRef: EdsDirectoryItemRef,
         outDirItemInfo: *mut EdsDirectoryItemInfo,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsDeleteDirectoryItem(inDirItemRef: Wrapper<EdsDirectoryItemRef>) -> EdsError;
+    fn EdsDeleteDirectoryItem(inDirItemRef: EdsDirectoryItemRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsDownload(
-        inDirItemRef: Wrapper<EdsDirectoryItemRef>,
+        inDirItemRef: EdsDirectoryItemRef,
         inReadSize: EdsUInt64,
-        outStream: Wrapper<EdsStreamRef>,
-    ) -> EdsError;
+        outStream: EdsStreamRef,
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsDownloadCancel(inDirItemRef: Wrapper<EdsDirectoryItemRef>) -> EdsError;
+    fn EdsDownloadCancel(inDirItemRef: EdsDirectoryItemRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsDownloadComplete(inDirItemRef: Wrapper<EdsDirectoryItemRef>) -> EdsError;
+    fn EdsDownloadComplete(inDirItemRef: EdsDirectoryItemRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsDownloadThumbnail(
-        inDirItemRef: Wrapper<EdsDirectoryItemRef>,
-        outStream: Wrapper<EdsStreamRef>,
-    ) -> EdsError;
+        inDirItemRef: EdsDirectoryItemRef,
+        outStream: EdsStreamRef,
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsGetAttribute(
-        inDirItemRef: Wrapper<EdsDirectoryItemRef>,
+        inDirItemRef: EdsDirectoryItemRef,
         outFileAttribute: *mut EdsFileAttributes,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetAttribute(
-        inDirItemRef: Wrapper<EdsDirectoryItemRef>,
+        inDirItemRef: EdsDirectoryItemRef,
         inFileAttribute: EdsFileAttributes,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetMetaImage(
-        inDirItemRef: Wrapper<EdsDirectoryItemRef>,
+        inDirItemRef: EdsDirectoryItemRef,
         inMetaType: EdsUInt32,
         inMetaDataSize: EdsUInt32,
         inMetaData: *const EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Stream operating functions"]
@@ -1724,10 +1990,11 @@ unsafe extern "C" {
         inCreateDisposition: EdsFileCreateDisposition,
         inDesiredAccess: EdsAccess,
         outStream: *mut EdsStreamRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsCreateMemoryStream(inBufferSize: EdsUInt64, outStream: *mut EdsStreamRef) -> EdsError;
+    fn EdsCreateMemoryStream(inBufferSize: EdsUInt64, outStream: *mut EdsStreamRef)
+    -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsCreateFileStreamEx(
@@ -1735,75 +2002,72 @@ unsafe extern "C" {
         inCreateDisposition: EdsFileCreateDisposition,
         inDesiredAccess: EdsAccess,
         outStream: *mut EdsStreamRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsCreateMemoryStreamFromPointer(
         inUserBuffer: *mut EdsVoid,
         inBufferSize: EdsUInt64,
         outStream: *mut EdsStreamRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsGetPointer(inStream: Wrapper<EdsStreamRef>, outPointer: *mut EdsBaseRef) -> EdsError;
+    fn EdsGetPointer(inStream: EdsStreamRef, outPointer: *mut EdsBaseRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsRead(
-        inStreamRef: Wrapper<EdsStreamRef>,
+        inStreamRef: EdsStreamRef,
         inReadSize: EdsUInt64,
         outBuffer: *mut EdsVoid,
         outReadSize: *mut EdsUInt64,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsWrite(
-        inStreamRef: Wrapper<EdsStreamRef>,
+        inStreamRef: EdsStreamRef,
         inWriteSize: EdsUInt64,
         inBuffer: *const EdsVoid,
         outWrittenSize: *mut EdsUInt64,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSeek(
-        inStreamRef: Wrapper<EdsStreamRef>,
+        inStreamRef: EdsStreamRef,
         inSeekOffset: EdsInt64,
         inSeekOrigin: EdsSeekOrigin,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsGetPosition(inStreamRef: Wrapper<EdsStreamRef>, outPosition: *mut EdsUInt64) -> EdsError;
+    fn EdsGetPosition(inStreamRef: EdsStreamRef, outPosition: *mut EdsUInt64) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsGetLength(inStreamRef: Wrapper<EdsStreamRef>, outLength: *mut EdsUInt64) -> EdsError;
+    fn EdsGetLength(inStreamRef: EdsStreamRef, outLength: *mut EdsUInt64) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsCopyData(
-        inStreamRef: Wrapper<EdsStreamRef>,
+        inStreamRef: EdsStreamRef,
         inWriteSize: EdsUInt64,
         outStreamRef: EdsStreamRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetProgressCallback(
-        inRef: Wrapper<EdsBaseRef>,
+        inRef: EdsBaseRef,
         inProgressCallback: EdsProgressCallback,
         inProgressOption: EdsProgressOption,
-        inContext: Wrapper<*mut EdsVoid>,
-    ) -> EdsError;
+        inContext: *mut EdsVoid,
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Image operating functions"]
-    fn EdsCreateImageRef(
-        inStreamRef: Wrapper<EdsStreamRef>,
-        outImageRef: *mut EdsImageRef,
-    ) -> EdsError;
+    fn EdsCreateImageRef(inStreamRef: EdsStreamRef, outImageRef: *mut EdsImageRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsGetImageInfo(
         inImageRef: EdsImageRef,
         inImageSource: EdsImageSource,
         outImageInfo: *mut EdsImageInfo,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsGetImage(
@@ -1813,61 +2077,61 @@ unsafe extern "C" {
         inSrcRect: EdsRect,
         inDstSize: EdsSize,
         outStreamRef: EdsStreamRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsCreateEvfImageRef(
-        inStreamRef: Wrapper<EdsStreamRef>,
+        inStreamRef: EdsStreamRef,
         outEvfImageRef: *mut EdsEvfImageRef,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsDownloadEvfImage(
-        inCameraRef: Wrapper<EdsCameraRef>,
-        inEvfImageRef: Wrapper<EdsEvfImageRef>,
-    ) -> EdsError;
+        inCameraRef: EdsCameraRef,
+        inEvfImageRef: EdsEvfImageRef,
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     #[doc = "Event handler registering functions"]
     fn EdsSetCameraAddedHandler(
         inCameraAddedHandler: EdsCameraAddedHandler,
         inContext: *mut EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetPropertyEventHandler(
-        inCameraRef: Wrapper<EdsCameraRef>,
-        inEvnet: EdsPropertyEvent,
+        inCameraRef: EdsCameraRef,
+        inEvnet: EdsUInt32,
         inPropertyEventHandler: EdsPropertyEventHandler,
         inContext: *mut EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetObjectEventHandler(
-        inCameraRef: Wrapper<EdsCameraRef>,
-        inEvnet: EdsObjectEvent,
+        inCameraRef: EdsCameraRef,
+        inEvnet: EdsUInt32,
         inObjectEventHandler: EdsObjectEventHandler,
         inContext: *mut EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetCameraStateEventHandler(
-        inCameraRef: Wrapper<EdsCameraRef>,
-        inEvnet: EdsStateEvent,
+        inCameraRef: EdsCameraRef,
+        inEvnet: EdsUInt32,
         inStateEventHandler: EdsStateEventHandler,
         inContext: *mut EdsVoid,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsCreateStream(inStream: *mut EdsIStream, outStreamRef: *mut EdsStreamRef) -> EdsError;
+    fn EdsCreateStream(inStream: *mut EdsIStream, outStreamRef: *mut EdsStreamRef) -> EdsErrorCode;
 }
 unsafe extern "C" {
-    fn EdsGetEvent() -> EdsError;
+    fn EdsGetEvent() -> EdsErrorCode;
 }
 unsafe extern "C" {
     fn EdsSetFramePoint(
-        inCameraRef: Wrapper<EdsCameraRef>,
+        inCameraRef: EdsCameraRef,
         inFramepoint: EdsPoint,
         inLockAfFrame: EdsBool,
-    ) -> EdsError;
+    ) -> EdsErrorCode;
 }
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! Picture style selection and parameters.
+//!
+//! The parameters and the caption are properties taking the picture style they
+//! apply to as parameter, so those of any style can be read and written
+//! whichever style is active.
+
+use crate::*;
+
+impl EdsPictureStyle {
+    /// Whether the style is registered by the user, and has a caption.
+    pub fn is_user_defined(self) -> bool {
+        matches!(
+            self,
+            EdsPictureStyle::User1
+                | EdsPictureStyle::User2
+                | EdsPictureStyle::User3
+                | EdsPictureStyle::PC1
+                | EdsPictureStyle::PC2
+                | EdsPictureStyle::PC3
+        )
+    }
+
+    fn param(self) -> EdsInt32 {
+        EdsUInt32::from(self) as EdsInt32
+    }
+}
+
+/// Parameters of a picture style, as laid out by [`EdsPictureStyleDesc`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictureStyleDesc {
+    pub contrast: EdsInt32,
+    pub sharpness: EdsUInt32,
+    pub saturation: EdsInt32,
+    pub color_tone: EdsInt32,
+    /// Only used by [`EdsPictureStyle::Monochrome`].
+    pub filter_effect: EdsFilterEffect,
+    /// Only used by [`EdsPictureStyle::Monochrome`].
+    pub toning_effect: EdsToningEffect,
+    pub sharp_fineness: EdsUInt32,
+    pub sharp_threshold: EdsUInt32,
+}
+
+impl TryFrom<EdsPictureStyleDesc> for PictureStyleDesc {
+    type Error = Error;
+
+    /// Fails with `EdsError::PropertiesMismatch` on an unknown filter or
+    /// toning effect.
+    fn try_from(desc: EdsPictureStyleDesc) -> Result<Self, Error> {
+        Ok(Self {
+            contrast: desc.contrast,
+            sharpness: desc.sharpness,
+            saturation: desc.saturation,
+            color_tone: desc.color_tone,
+            filter_effect: EdsFilterEffect::try_from_primitive(desc.filter_effect)
+                .map_err(|_| Error::new(EdsError::PropertiesMismatch))?,
+            toning_effect: EdsToningEffect::try_from_primitive(desc.toning_effect)
+                .map_err(|_| Error::new(EdsError::PropertiesMismatch))?,
+            sharp_fineness: desc.sharp_fineness,
+            sharp_threshold: desc.sharp_threshold,
+        })
+    }
+}
+
+impl From<PictureStyleDesc> for EdsPictureStyleDesc {
+    fn from(desc: PictureStyleDesc) -> Self {
+        Self {
+            contrast: desc.contrast,
+            sharpness: desc.sharpness,
+            saturation: desc.saturation,
+            color_tone: desc.color_tone,
+            filter_effect: desc.filter_effect.into(),
+            toning_effect: desc.toning_effect.into(),
+            sharp_fineness: desc.sharp_fineness,
+            sharp_threshold: desc.sharp_threshold,
+        }
+    }
+}
+
+impl PropertyType for PictureStyleDesc {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        EdsPictureStyleDesc::from_value(value)?.try_into()
+    }
+
+    fn into_value(self) -> PropertyValue {
+        PropertyValue::PictureStyleDesc(self.into())
+    }
+}
+
+impl CameraSession {
+    pub async fn get_picture_style(&self) -> Result<EdsPictureStyle, Error> {
+        self.get::<props::PictureStyle>().await
+    }
+
+    pub async fn get_all_picture_style(&self) -> Result<Vec<EdsPictureStyle>, Error> {
+        Ok(self.describe::<props::PictureStyle>().await?.values)
+    }
+
+    pub async fn set_picture_style(&self, style: EdsPictureStyle) -> Result<(), Error> {
+        self.set::<props::PictureStyle>(style).await
+    }
+
+    /// Parameters of `style`, active or not.
+    pub async fn get_picture_style_desc(
+        &self,
+        style: EdsPictureStyle,
+    ) -> Result<PictureStyleDesc, Error> {
+        self.get_with_param::<props::PictureStyleDesc>(style.param())
+            .await
+    }
+
+    /// Sets the parameters of `style`, active or not. Values out of the range
+    /// of the camera are rejected by it.
+    pub async fn set_picture_style_desc(
+        &self,
+        style: EdsPictureStyle,
+        desc: PictureStyleDesc,
+    ) -> Result<(), Error> {
+        self.set_with_param::<props::PictureStyleDesc>(style.param(), desc)
+            .await
+    }
+
+    /// Caption of the active style, which must be user defined. See
+    /// [`CameraSession::get_picture_style_caption_of`].
+    pub async fn get_picture_style_caption(&self) -> Result<String, Error> {
+        let style = self.get_picture_style().await?;
+        self.get_picture_style_caption_of(style).await
+    }
+
+    /// Caption of a user defined style, see [`EdsPictureStyle::is_user_defined`].
+    pub async fn get_picture_style_caption_of(
+        &self,
+        style: EdsPictureStyle,
+    ) -> Result<String, Error> {
+        if !style.is_user_defined() {
+            return Err(EdsError::InvalidParameter.into());
+        }
+        self.get_with_param::<props::PictureStyleCaption>(style.param())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monochrome() -> PictureStyleDesc {
+        PictureStyleDesc {
+            contrast: -2,
+            sharpness: 3,
+            saturation: 0,
+            color_tone: 1,
+            filter_effect: EdsFilterEffect::Orange,
+            toning_effect: EdsToningEffect::Sepia,
+            sharp_fineness: 4,
+            sharp_threshold: 5,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let desc = monochrome();
+        let raw = EdsPictureStyleDesc::from(desc);
+        assert_eq!(raw.filter_effect, 2);
+        assert_eq!(raw.toning_effect, 1);
+        assert_eq!(PictureStyleDesc::try_from(raw).unwrap(), desc);
+        assert_eq!(
+            PictureStyleDesc::from_value(desc.into_value()).unwrap(),
+            desc
+        );
+    }
+
+    #[test]
+    fn unknown_effects() {
+        let raw = EdsPictureStyleDesc {
+            filter_effect: 9,
+            ..monochrome().into()
+        };
+        assert!(PictureStyleDesc::try_from(raw).is_err());
+        let raw = EdsPictureStyleDesc {
+            toning_effect: 9,
+            ..monochrome().into()
+        };
+        assert!(PictureStyleDesc::try_from(raw).is_err());
+    }
+}
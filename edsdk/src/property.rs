@@ -0,0 +1,778 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::*;
+use std::{mem::MaybeUninit, mem::size_of, ptr};
+
+/// Value of a property, in the data type reported by `EdsGetPropertySize`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    String(String),
+    Int8(EdsInt8),
+    UInt8(EdsUInt8),
+    Int16(EdsInt16),
+    UInt16(EdsUInt16),
+    Int32(EdsInt32),
+    UInt32(EdsUInt32),
+    Int64(EdsInt64),
+    UInt64(EdsUInt64),
+    Float(EdsFloat),
+    Double(EdsDouble),
+    ByteBlock(Vec<u8>),
+    Rational(EdsRational),
+    Point(EdsPoint),
+    Rect(EdsRect),
+    Time(EdsTime),
+    BoolArray(Vec<bool>),
+    Int8Array(Vec<EdsInt8>),
+    Int16Array(Vec<EdsInt16>),
+    Int32Array(Vec<EdsInt32>),
+    UInt8Array(Vec<EdsUInt8>),
+    UInt16Array(Vec<EdsUInt16>),
+    UInt32Array(Vec<EdsUInt32>),
+    RationalArray(Vec<EdsRational>),
+    FocusInfo(Box<EdsFocusInfo>),
+    PictureStyleDesc(EdsPictureStyleDesc),
+    /// Raw data of a property whose type is unknown.
+    Unknown(Vec<u8>),
+}
+
+/// C types for which any bit pattern is a valid value, and without padding.
+///
+/// # Safety
+/// Implementors must uphold both properties.
+unsafe trait Plain: Copy {}
+
+macro_rules! plain {
+    ($($t:ty),*) => {
+        $(unsafe impl Plain for $t {})*
+    };
+}
+
+plain!(
+    EdsInt8,
+    EdsUInt8,
+    EdsInt16,
+    EdsUInt16,
+    EdsInt32,
+    EdsUInt32,
+    EdsInt64,
+    EdsUInt64,
+    EdsFloat,
+    EdsDouble,
+    EdsRational,
+    EdsPoint,
+    EdsRect,
+    EdsTime,
+    EdsFocusInfo,
+    EdsPictureStyleDesc
+);
+
+/// Size of an element of a `BoolArray`: `EdsBool` is an `int` in the C headers.
+const EDS_BOOL_SIZE: usize = size_of::<EdsUInt32>();
+
+/// Reads a `T`, the missing trailing bytes being zeroed.
+fn read_padded<T: Plain>(bytes: &[u8]) -> T {
+    let mut value = MaybeUninit::<T>::zeroed();
+    let len = bytes.len().min(size_of::<T>());
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), value.as_mut_ptr() as *mut u8, len);
+        value.assume_init()
+    }
+}
+
+fn read<T: Plain>(bytes: &[u8]) -> Result<T, Error> {
+    if bytes.len() != size_of::<T>() {
+        return Err(EdsError::PropertiesMismatch.into());
+    }
+    Ok(read_padded(bytes))
+}
+
+/// Splits `bytes` into elements of `size` bytes, failing if some are left over.
+fn chunks(bytes: &[u8], size: usize) -> Result<std::slice::ChunksExact<'_, u8>, Error> {
+    if !bytes.len().is_multiple_of(size) {
+        return Err(EdsError::PropertiesMismatch.into());
+    }
+    Ok(bytes.chunks_exact(size))
+}
+
+fn read_array<T: Plain>(bytes: &[u8]) -> Result<Vec<T>, Error> {
+    Ok(chunks(bytes, size_of::<T>())?.map(read_padded).collect())
+}
+
+fn bytes_of<T: Plain>(value: &T) -> &[u8] {
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) }
+}
+
+fn array_bytes<T: Plain>(values: &[T]) -> Vec<u8> {
+    values.iter().flat_map(|v| bytes_of(v).to_vec()).collect()
+}
+
+fn bool_bytes(value: bool, size: usize) -> Vec<u8> {
+    if size == size_of::<u8>() {
+        vec![value as u8]
+    } else {
+        (value as EdsUInt32).to_ne_bytes().to_vec()
+    }
+}
+
+impl PropertyValue {
+    pub fn data_type(&self) -> EdsDataType {
+        use PropertyValue::*;
+        match self {
+            Bool(_) => EdsDataType::Bool,
+            String(_) => EdsDataType::String,
+            Int8(_) => EdsDataType::Int8,
+            UInt8(_) => EdsDataType::UInt8,
+            Int16(_) => EdsDataType::Int16,
+            UInt16(_) => EdsDataType::UInt16,
+            Int32(_) => EdsDataType::Int32,
+            UInt32(_) => EdsDataType::UInt32,
+            Int64(_) => EdsDataType::Int64,
+            UInt64(_) => EdsDataType::UInt64,
+            Float(_) => EdsDataType::Float,
+            Double(_) => EdsDataType::Double,
+            ByteBlock(_) => EdsDataType::ByteBlock,
+            Rational(_) => EdsDataType::Rational,
+            Point(_) => EdsDataType::Point,
+            Rect(_) => EdsDataType::Rect,
+            Time(_) => EdsDataType::Time,
+            BoolArray(_) => EdsDataType::BoolArray,
+            Int8Array(_) => EdsDataType::Int8Array,
+            Int16Array(_) => EdsDataType::Int16Array,
+            Int32Array(_) => EdsDataType::Int32Array,
+            UInt8Array(_) => EdsDataType::Uint8Array,
+            UInt16Array(_) => EdsDataType::Uint16Array,
+            UInt32Array(_) => EdsDataType::Uint32Array,
+            RationalArray(_) => EdsDataType::RationalArray,
+            FocusInfo(_) => EdsDataType::FocusInfo,
+            PictureStyleDesc(_) => EdsDataType::PictureStyleDesc,
+            Unknown(_) => EdsDataType::Unknown,
+        }
+    }
+
+    /// Decodes the data returned by `EdsGetPropertyData` for a property of type
+    /// `data_type`. Fails with `EdsError::PropertiesMismatch` if the size of
+    /// `bytes` does not match the type, or is not a multiple of the size of an
+    /// element for arrays.
+    pub fn decode(data_type: EdsDataType, bytes: &[u8]) -> Result<Self, Error> {
+        use EdsDataType::*;
+        let value = match data_type {
+            Unknown => PropertyValue::Unknown(bytes.to_vec()),
+            Bool if bytes.len() != size_of::<u8>() && bytes.len() != EDS_BOOL_SIZE => {
+                return Err(EdsError::PropertiesMismatch.into());
+            }
+            Bool => PropertyValue::Bool(bytes.iter().any(|b| *b != 0)),
+            String => PropertyValue::String(eds_string(bytes)),
+            Int8 => PropertyValue::Int8(read(bytes)?),
+            UInt8 => PropertyValue::UInt8(read(bytes)?),
+            Int16 => PropertyValue::Int16(read(bytes)?),
+            UInt16 => PropertyValue::UInt16(read(bytes)?),
+            Int32 => PropertyValue::Int32(read(bytes)?),
+            UInt32 => PropertyValue::UInt32(read(bytes)?),
+            Int64 => PropertyValue::Int64(read(bytes)?),
+            UInt64 => PropertyValue::UInt64(read(bytes)?),
+            Float => PropertyValue::Float(read(bytes)?),
+            Double => PropertyValue::Double(read(bytes)?),
+            ByteBlock => PropertyValue::ByteBlock(bytes.to_vec()),
+            Rational => PropertyValue::Rational(read(bytes)?),
+            Point => PropertyValue::Point(read(bytes)?),
+            Rect => PropertyValue::Rect(read(bytes)?),
+            Time => PropertyValue::Time(read(bytes)?),
+            BoolArray => PropertyValue::BoolArray(
+                chunks(bytes, EDS_BOOL_SIZE)?
+                    .map(|b| b.iter().any(|b| *b != 0))
+                    .collect(),
+            ),
+            Int8Array => PropertyValue::Int8Array(read_array(bytes)?),
+            Int16Array => PropertyValue::Int16Array(read_array(bytes)?),
+            Int32Array => PropertyValue::Int32Array(read_array(bytes)?),
+            Uint8Array => PropertyValue::UInt8Array(read_array(bytes)?),
+            Uint16Array => PropertyValue::UInt16Array(read_array(bytes)?),
+            Uint32Array => PropertyValue::UInt32Array(read_array(bytes)?),
+            RationalArray => PropertyValue::RationalArray(read_array(bytes)?),
+            FocusInfo if bytes.len() > size_of::<EdsFocusInfo>() => {
+                return Err(EdsError::PropertiesMismatch.into());
+            }
+            // Cameras only fill the focus points they have.
+            FocusInfo => PropertyValue::FocusInfo(Box::new(read_padded(bytes))),
+            PictureStyleDesc => PropertyValue::PictureStyleDesc(read(bytes)?),
+        };
+        Ok(value)
+    }
+
+    /// Encodes the value for `EdsSetPropertyData`. `size` is the size reported by
+    /// `EdsGetPropertySize`, only used to tell the size of an `EdsBool`.
+    pub fn encode(&self, size: usize) -> Vec<u8> {
+        use PropertyValue::*;
+        match self {
+            Bool(v) => bool_bytes(*v, size),
+            String(v) => {
+                let mut bytes = v.as_bytes().to_vec();
+                bytes.push(0);
+                bytes
+            }
+            Int8(v) => bytes_of(v).to_vec(),
+            UInt8(v) => bytes_of(v).to_vec(),
+            Int16(v) => bytes_of(v).to_vec(),
+            UInt16(v) => bytes_of(v).to_vec(),
+            Int32(v) => bytes_of(v).to_vec(),
+            UInt32(v) => bytes_of(v).to_vec(),
+            Int64(v) => bytes_of(v).to_vec(),
+            UInt64(v) => bytes_of(v).to_vec(),
+            Float(v) => bytes_of(v).to_vec(),
+            Double(v) => bytes_of(v).to_vec(),
+            ByteBlock(v) | UInt8Array(v) | Unknown(v) => v.clone(),
+            Rational(v) => bytes_of(v).to_vec(),
+            Point(v) => bytes_of(v).to_vec(),
+            Rect(v) => bytes_of(v).to_vec(),
+            Time(v) => bytes_of(v).to_vec(),
+            BoolArray(v) => v
+                .iter()
+                .flat_map(|b| bool_bytes(*b, EDS_BOOL_SIZE))
+                .collect(),
+            Int8Array(v) => array_bytes(v),
+            Int16Array(v) => array_bytes(v),
+            Int32Array(v) => array_bytes(v),
+            UInt16Array(v) => array_bytes(v),
+            UInt32Array(v) => array_bytes(v),
+            RationalArray(v) => array_bytes(v),
+            FocusInfo(v) => bytes_of(v.as_ref()).to_vec(),
+            PictureStyleDesc(v) => bytes_of(v).to_vec(),
+        }
+    }
+}
+
+/// Rust type a property value is converted to and from.
+pub trait PropertyType: Sized {
+    /// Fails with `EdsError::PropertiesMismatch` if `value` is of another type.
+    fn from_value(value: PropertyValue) -> Result<Self, Error>;
+    fn into_value(self) -> PropertyValue;
+}
+
+/// Property of a camera or an image, with the type of its value.
+///
+/// The implementations, one per [`EdsPropertyID`], are in [`props`].
+pub trait Property {
+    const ID: EdsPropertyID;
+    type Value: PropertyType;
+}
+
+impl PropertyType for PropertyValue {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        Ok(value)
+    }
+
+    fn into_value(self) -> PropertyValue {
+        self
+    }
+}
+
+macro_rules! property_type {
+    ($($t:ty => $variant:ident),* $(,)?) => {$(
+        impl PropertyType for $t {
+            fn from_value(value: PropertyValue) -> Result<Self, Error> {
+                match value {
+                    PropertyValue::$variant(v) => Ok(v),
+                    _ => Err(EdsError::PropertiesMismatch.into()),
+                }
+            }
+
+            fn into_value(self) -> PropertyValue {
+                PropertyValue::$variant(self)
+            }
+        }
+    )*};
+}
+
+property_type!(
+    bool => Bool,
+    String => String,
+    EdsInt8 => Int8,
+    EdsUInt8 => UInt8,
+    EdsInt16 => Int16,
+    EdsUInt16 => UInt16,
+    EdsInt32 => Int32,
+    EdsUInt32 => UInt32,
+    EdsInt64 => Int64,
+    EdsUInt64 => UInt64,
+    EdsFloat => Float,
+    EdsDouble => Double,
+    Vec<u8> => ByteBlock,
+    EdsRational => Rational,
+    EdsPoint => Point,
+    EdsRect => Rect,
+    EdsTime => Time,
+    Vec<bool> => BoolArray,
+    Vec<EdsInt8> => Int8Array,
+    Vec<EdsInt16> => Int16Array,
+    Vec<EdsInt32> => Int32Array,
+    Vec<EdsUInt16> => UInt16Array,
+    Vec<EdsUInt32> => UInt32Array,
+    Vec<EdsRational> => RationalArray,
+    Box<EdsFocusInfo> => FocusInfo,
+    EdsPictureStyleDesc => PictureStyleDesc,
+);
+
+/// Implements [`PropertyType`] for enums stored as an integer property.
+macro_rules! property_enum {
+    ($($t:ty),* $(,)?) => {$(
+        impl PropertyType for $t {
+            fn from_value(value: PropertyValue) -> Result<Self, Error> {
+                let primitive = PropertyType::from_value(value)?;
+                <$t>::try_from_primitive(primitive)
+                    .map_err(|_| Error::new(EdsError::PropertiesMismatch))
+            }
+
+            fn into_value(self) -> PropertyValue {
+                <$t as TryFromPrimitive>::Primitive::from(self).into_value()
+            }
+        }
+    )*};
+}
+
+property_enum!(
+    EdsSaveTo,
+    EdsImageQuality,
+    EdsWhiteBalance,
+    EdsISO,
+    EdsAv,
+    EdsTv,
+    EdsExposureCompensation,
+    EdsDriveMode,
+    EdsMeteringMode,
+    EdsAFMode,
+    EdsFlashType,
+    EdsFlashSyncTiming,
+    EdsRedEye,
+    EdsPictureStyle,
+    EdsNoiseReduction,
+    EdsOrientation,
+    EdsBatteryQuality,
+    EdsEvfOutputDevice,
+    EdsEvfMode,
+    EdsEvfDepthOfFieldPreview,
+);
+
+/// Decodes the raw data of [`EdsPropertyID::FlashMode`], so that it is read
+/// and written like the settings stored as a single integer.
+impl TryFromPrimitive for EdsFlashMode {
+    type Primitive = [EdsUInt32; 2];
+    type Error = num_enum::TryFromPrimitiveError<Self>;
+
+    const NAME: &'static str = "EdsFlashMode";
+
+    fn try_from_primitive(number: Self::Primitive) -> Result<Self, Self::Error> {
+        let [flash_type, sync_timing] = number;
+        match (
+            EdsFlashType::try_from_primitive(flash_type),
+            EdsFlashSyncTiming::try_from_primitive(sync_timing),
+        ) {
+            (Ok(flash_type), Ok(sync_timing)) => Ok(Self {
+                flash_type,
+                sync_timing,
+            }),
+            _ => Err(num_enum::TryFromPrimitiveError::new(number)),
+        }
+    }
+}
+
+impl From<EdsFlashMode> for [EdsUInt32; 2] {
+    fn from(mode: EdsFlashMode) -> Self {
+        [mode.flash_type.into(), mode.sync_timing.into()]
+    }
+}
+
+impl PropertyType for EdsFlashMode {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        match Vec::<EdsUInt32>::from_value(value)?[..] {
+            [flash_type, sync_timing, ..] => Self::try_from_primitive([flash_type, sync_timing])
+                .map_err(|_| Error::new(EdsError::PropertiesMismatch)),
+            _ => Err(EdsError::PropertiesMismatch.into()),
+        }
+    }
+
+    fn into_value(self) -> PropertyValue {
+        PropertyValue::UInt32Array(<[EdsUInt32; 2]>::from(self).to_vec())
+    }
+}
+
+/// Values a property can currently be set to, as described by `EdsGetPropertyDesc`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDescriptor<T> {
+    /// `form` field of the descriptor, passed as is.
+    pub form: EdsInt32,
+    pub access: EdsAccess,
+    /// Allowed values, in the order given by the camera.
+    pub values: Vec<T>,
+    /// Allowed values that could not be decoded as `T`.
+    pub undecoded: Vec<EdsInt32>,
+}
+
+impl<T> PropertyDescriptor<T> {
+    pub fn is_writable(&self) -> bool {
+        matches!(self.access, EdsAccess::Write | EdsAccess::ReadWrite)
+    }
+}
+
+impl<T: PropertyType> PropertyDescriptor<T> {
+    fn from_codes(form: EdsInt32, access: EdsAccess, codes: &[EdsInt32]) -> Self {
+        let mut values = vec![];
+        let mut undecoded = vec![];
+        for &code in codes {
+            match decode_desc_value(code) {
+                Some(value) => values.push(value),
+                None => undecoded.push(code),
+            }
+        }
+        Self {
+            form,
+            access,
+            values,
+            undecoded,
+        }
+    }
+}
+
+impl PropertyDescriptor<EdsInt32> {
+    /// Decodes the raw codes of a descriptor as `T`.
+    pub(crate) fn decode<T: PropertyType>(&self) -> PropertyDescriptor<T> {
+        let codes: Vec<_> = self.values.iter().chain(&self.undecoded).copied().collect();
+        PropertyDescriptor::from_codes(self.form, self.access, &codes)
+    }
+}
+
+/// Decodes a value of a property descriptor, which the SDK always gives as an
+/// `EdsInt32` whatever the type of the property.
+fn decode_desc_value<T: PropertyType>(code: EdsInt32) -> Option<T> {
+    T::from_value(PropertyValue::UInt32(code as EdsUInt32))
+        .or_else(|_| T::from_value(PropertyValue::Int32(code)))
+        .ok()
+}
+
+/// Reads the descriptor of a property with `EdsGetPropertyDesc`.
+pub async fn describe_property<T, R>(
+    in_ref: &R,
+    prop_id: EdsPropertyID,
+) -> Result<PropertyDescriptor<T>, Error>
+where
+    T: PropertyType,
+    R: EdsPropertyHolder,
+{
+    read_descriptor(in_ref.as_base_ref(), prop_id)
+}
+
+pub(crate) fn read_descriptor<T: PropertyType>(
+    in_ref: EdsBaseRef,
+    prop_id: EdsPropertyID,
+) -> Result<PropertyDescriptor<T>, Error> {
+    let mut desc = EdsPropertyDesc::default();
+    check_call!(EdsGetPropertyDesc(in_ref, prop_id.into(), &mut desc))?;
+    let count = (desc.num_elements.max(0) as usize).min(desc.prop_desc.len());
+    Ok(PropertyDescriptor::from_codes(
+        desc.form,
+        EdsAccess::try_from_primitive(desc.access as u32).unwrap_or(EdsAccess::Error),
+        &desc.prop_desc[..count],
+    ))
+}
+
+/// Reads a property of any type, sizing the buffer from `EdsGetPropertySize`.
+pub async fn get_property<R>(in_ref: &R, prop_id: EdsPropertyID) -> Result<PropertyValue, Error>
+where
+    R: EdsPropertyHolder,
+{
+    get_property_with_param(in_ref, prop_id, 0).await
+}
+
+/// Reads the property `P` of any holder, checking the type reported by the SDK,
+/// e.g. `get::<props::EvfZoom>(&evf_image)` for a live view image.
+pub async fn get<P: Property>(in_ref: &impl EdsPropertyHolder) -> Result<P::Value, Error> {
+    P::Value::from_value(get_property(in_ref, P::ID).await?)
+}
+
+/// Reads a property taking a parameter, e.g. the picture style of
+/// [`EdsPropertyID::PictureStyleDesc`].
+pub async fn get_property_with_param<R>(
+    in_ref: &R,
+    prop_id: EdsPropertyID,
+    param: EdsInt32,
+) -> Result<PropertyValue, Error>
+where
+    R: EdsPropertyHolder,
+{
+    read_property(in_ref.as_base_ref(), prop_id, param)
+}
+
+pub(crate) fn read_property(
+    in_ref: EdsBaseRef,
+    prop_id: EdsPropertyID,
+    param: EdsInt32,
+) -> Result<PropertyValue, Error> {
+    let mut data_type = EdsUInt32::default();
+    let mut size = EdsUInt32::default();
+    check_call!(EdsGetPropertySize(
+        in_ref,
+        prop_id.into(),
+        param,
+        &mut data_type,
+        &mut size
+    ))?;
+    let mut buffer = vec![0u8; size as usize];
+    check_call!(EdsGetPropertyData(
+        in_ref,
+        prop_id.into(),
+        param,
+        size,
+        buffer.as_mut_ptr() as *mut EdsVoid,
+    ))?;
+    PropertyValue::decode(EdsDataType::from(data_type), &buffer)
+}
+
+/// Writes a property. Fails with `EdsError::PropertiesMismatch` if `value` is not
+/// of the type reported by `EdsGetPropertySize`.
+pub async fn set_property<R>(
+    in_ref: &R,
+    prop_id: EdsPropertyID,
+    value: &PropertyValue,
+) -> Result<(), Error>
+where
+    R: EdsPropertyHolder,
+{
+    set_property_with_param(in_ref, prop_id, 0, value).await
+}
+
+/// Writes a property taking a parameter. See [`get_property_with_param`].
+pub async fn set_property_with_param<R>(
+    in_ref: &R,
+    prop_id: EdsPropertyID,
+    param: EdsInt32,
+    value: &PropertyValue,
+) -> Result<(), Error>
+where
+    R: EdsPropertyHolder,
+{
+    let (data_type, size) = eds_get_property_size(in_ref, prop_id, param).await?;
+    if value.data_type() != data_type {
+        return Err(EdsError::PropertiesMismatch.into());
+    }
+    let data = value.encode(size as usize);
+    eds_set_property_data(in_ref, prop_id, param, &data).await
+}
+
+impl CameraSession {
+    /// Reads the descriptor of the property `P`, decoding the allowed values.
+    pub async fn describe<P: Property>(&self) -> Result<PropertyDescriptor<P::Value>, Error> {
+        describe_property(self, P::ID).await
+    }
+
+    pub async fn get_property(&self, prop_id: EdsPropertyID) -> Result<PropertyValue, Error> {
+        get_property(self, prop_id).await
+    }
+
+    pub async fn set_property(
+        &self,
+        prop_id: EdsPropertyID,
+        value: &PropertyValue,
+    ) -> Result<(), Error> {
+        set_property(self, prop_id, value).await
+    }
+
+    /// Reads the property `P`, checking the type reported by the camera.
+    pub async fn get<P: Property>(&self) -> Result<P::Value, Error> {
+        get::<P>(self).await
+    }
+
+    /// Writes the property `P`, checking the type reported by the camera.
+    pub async fn set<P: Property>(&self, value: P::Value) -> Result<(), Error> {
+        set_property(self, P::ID, &value.into_value()).await
+    }
+
+    /// Reads the property `P` for `param`. See [`get_property_with_param`].
+    pub async fn get_with_param<P: Property>(&self, param: EdsInt32) -> Result<P::Value, Error> {
+        P::Value::from_value(get_property_with_param(self, P::ID, param).await?)
+    }
+
+    /// Writes the property `P` for `param`. See [`get_property_with_param`].
+    pub async fn set_with_param<P: Property>(
+        &self,
+        param: EdsInt32,
+        value: P::Value,
+    ) -> Result<(), Error> {
+        set_property_with_param(self, P::ID, param, &value.into_value()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: PropertyValue, size: usize) {
+        let bytes = value.encode(size);
+        assert_eq!(bytes.len(), size, "{value:?}");
+        assert_eq!(
+            PropertyValue::decode(value.data_type(), &bytes).unwrap(),
+            value
+        );
+    }
+
+    /// Decodes `bytes` as `data_type`, then checks it encodes back to them.
+    fn bytes_round_trip(data_type: EdsDataType, bytes: &[u8]) {
+        let value = PropertyValue::decode(data_type, bytes).unwrap();
+        assert_eq!(value.data_type(), data_type);
+        assert_eq!(value.encode(bytes.len()), bytes, "{data_type:?}");
+    }
+
+    fn mismatch(data_type: EdsDataType, bytes: &[u8]) {
+        let err = PropertyValue::decode(data_type, bytes).unwrap_err();
+        assert_eq!(err.code(), EdsError::PropertiesMismatch, "{data_type:?}");
+    }
+
+    #[test]
+    fn scalars() {
+        round_trip(PropertyValue::Bool(true), 4);
+        round_trip(PropertyValue::Bool(false), 4);
+        round_trip(PropertyValue::Bool(true), 1);
+        round_trip(PropertyValue::String("EOS R5".into()), 7);
+        round_trip(PropertyValue::String(String::new()), 1);
+        round_trip(PropertyValue::Int8(-5), 1);
+        round_trip(PropertyValue::UInt8(200), 1);
+        round_trip(PropertyValue::Int16(-300), 2);
+        round_trip(PropertyValue::UInt16(60000), 2);
+        round_trip(PropertyValue::Int32(-70000), 4);
+        round_trip(PropertyValue::UInt32(0xDEAD_BEEF), 4);
+        round_trip(PropertyValue::Int64(-1 << 40), 8);
+        round_trip(PropertyValue::UInt64(1 << 60), 8);
+        round_trip(PropertyValue::Float(1.5), 4);
+        round_trip(PropertyValue::Double(-2.25), 8);
+        round_trip(PropertyValue::ByteBlock(vec![1, 2, 3]), 3);
+        round_trip(PropertyValue::Unknown(vec![9, 8]), 2);
+    }
+
+    #[test]
+    fn structures() {
+        round_trip(
+            PropertyValue::Rational(EdsRational {
+                numerator: -1,
+                denominator: 3,
+            }),
+            8,
+        );
+        round_trip(PropertyValue::Point(EdsPoint { x: 1, y: -2 }), 8);
+        round_trip(
+            PropertyValue::Rect(EdsRect {
+                point: EdsPoint { x: 10, y: 20 },
+                size: EdsSize {
+                    width: 640,
+                    height: 480,
+                },
+            }),
+            16,
+        );
+        round_trip(
+            PropertyValue::Time(EdsTime {
+                year: 2025,
+                month: 6,
+                day: 21,
+                hour: 13,
+                minute: 45,
+                second: 30,
+                milliseconds: 250,
+            }),
+            28,
+        );
+        let desc: Vec<u8> = (1..=size_of::<EdsPictureStyleDesc>() as u8).collect();
+        bytes_round_trip(EdsDataType::PictureStyleDesc, &desc);
+        let focus: Vec<u8> = (0..size_of::<EdsFocusInfo>()).map(|i| i as u8).collect();
+        bytes_round_trip(EdsDataType::FocusInfo, &focus);
+    }
+
+    #[test]
+    fn arrays() {
+        round_trip(PropertyValue::BoolArray(vec![true, false, true]), 12);
+        round_trip(PropertyValue::Int8Array(vec![-1, 0, 1]), 3);
+        round_trip(PropertyValue::Int16Array(vec![-300, 300]), 4);
+        round_trip(PropertyValue::Int32Array(vec![-70000, 1, 70000]), 12);
+        round_trip(PropertyValue::UInt8Array(vec![0, 255]), 2);
+        round_trip(PropertyValue::UInt16Array(vec![1, 60000]), 4);
+        round_trip(PropertyValue::UInt32Array(vec![3, 0xFFFF_FFFF]), 8);
+        round_trip(
+            PropertyValue::RationalArray(vec![
+                EdsRational {
+                    numerator: 1,
+                    denominator: 3,
+                },
+                EdsRational {
+                    numerator: 2,
+                    denominator: 1,
+                },
+            ]),
+            16,
+        );
+        round_trip(PropertyValue::UInt32Array(Vec::new()), 0);
+    }
+
+    #[test]
+    fn short_focus_info_is_padded() {
+        let PropertyValue::FocusInfo(info) = PropertyValue::decode(
+            EdsDataType::FocusInfo,
+            &[0; size_of::<EdsRect>() + size_of::<EdsUInt32>()],
+        )
+        .unwrap() else {
+            panic!("not a focus info");
+        };
+        assert_eq!(info.point_number, 0);
+        assert_eq!(info.focus_point[1052], info.focus_point[0]);
+    }
+
+    #[test]
+    fn short_buffers() {
+        mismatch(EdsDataType::Bool, &[]);
+        mismatch(EdsDataType::Int16, &[1]);
+        mismatch(EdsDataType::UInt32, &[1, 2, 3]);
+        mismatch(EdsDataType::Double, &[0; 4]);
+        mismatch(EdsDataType::Rational, &[0; 4]);
+        mismatch(EdsDataType::Time, &[0; 24]);
+        mismatch(
+            EdsDataType::PictureStyleDesc,
+            &[0; size_of::<EdsPictureStyleDesc>() - 1],
+        );
+    }
+
+    #[test]
+    fn oversized_buffers() {
+        mismatch(EdsDataType::Bool, &[0; 8]);
+        mismatch(EdsDataType::Int8, &[0; 2]);
+        mismatch(EdsDataType::UInt32, &[0; 8]);
+        mismatch(EdsDataType::Rect, &[0; 20]);
+        mismatch(EdsDataType::FocusInfo, &[0; size_of::<EdsFocusInfo>() + 1]);
+    }
+
+    #[test]
+    fn array_lengths() {
+        mismatch(EdsDataType::BoolArray, &[1, 0, 0, 0, 1]);
+        mismatch(EdsDataType::Int16Array, &[0; 3]);
+        mismatch(EdsDataType::Int32Array, &[0; 6]);
+        mismatch(EdsDataType::Uint16Array, &[0; 5]);
+        mismatch(EdsDataType::Uint32Array, &[0; 7]);
+        mismatch(EdsDataType::RationalArray, &[0; 12]);
+    }
+
+    #[test]
+    fn flash_mode() {
+        let mode = EdsFlashMode {
+            flash_type: EdsFlashType::ExternalETTL,
+            sync_timing: EdsFlashSyncTiming::SecondCurtain,
+        };
+        assert_eq!(<[EdsUInt32; 2]>::from(mode), [2, 1]);
+        assert_eq!(EdsFlashMode::try_from_primitive([2, 1]).unwrap(), mode);
+        assert!(EdsFlashMode::try_from_primitive([9, 0]).is_err());
+        assert_eq!(EdsFlashMode::from_value(mode.into_value()).unwrap(), mode);
+    }
+
+    #[test]
+    fn data_type_mismatch() {
+        assert!(u32::from_value(PropertyValue::Int32(1)).is_err());
+        assert!(String::from_value(PropertyValue::ByteBlock(Vec::new())).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! One [`Property`] per [`EdsPropertyID`], for
+//! [`CameraSession::get`](crate::CameraSession::get) and
+//! [`CameraSession::set`](crate::CameraSession::set).
+//!
+//! The `Evf*` properties describing a frame, such as [`EvfZoom`],
+//! [`EvfHistogram`] or [`EvfZoomRect`], are read from the live view image with
+//! [`get`] once downloaded, not from the session.
+//!
+//! Properties whose layout depends on the camera model are read as a raw
+//! [`PropertyValue`].
+
+use crate::*;
+
+macro_rules! properties {
+    ($($name:ident: $value:ty),* $(,)?) => {$(
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name;
+
+        impl Property for $name {
+            const ID: EdsPropertyID = EdsPropertyID::$name;
+            type Value = $value;
+        }
+    )*};
+}
+
+properties!(
+    ProductName: String,
+    OwnerName: String,
+    MakerName: String,
+    DateTime: EdsTime,
+    FirmwareVersion: String,
+    BatteryLevel: EdsInt32,
+    SaveTo: EdsSaveTo,
+    CurrentStorage: String,
+    CurrentFolder: String,
+    BatteryQuality: EdsBatteryQuality,
+    BodyIDEx: String,
+    HDDirectoryStructure: String,
+    ImageQuality: EdsImageQuality,
+    Orientation: EdsOrientation,
+    ICCProfile: Vec<u8>,
+    FocusInfo: Box<EdsFocusInfo>,
+    WhiteBalance: EdsWhiteBalance,
+    ColorTemperature: EdsUInt32,
+    WhiteBalanceShift: WbShift,
+    ColorSpace: EdsUInt32,
+    PictureStyle: EdsPictureStyle,
+    PictureStyleDesc: crate::PictureStyleDesc,
+    PictureStyleCaption: String,
+    GPSVersionID: PropertyValue,
+    GPSLatitudeRef: String,
+    GPSLatitude: Vec<EdsRational>,
+    GPSLongitudeRef: String,
+    GPSLongitude: Vec<EdsRational>,
+    GPSAltitudeRef: EdsUInt8,
+    GPSAltitude: EdsRational,
+    GPSTimeStamp: Vec<EdsRational>,
+    GPSSatellites: String,
+    GPSStatus: String,
+    GPSMapDatum: String,
+    GPSDateStamp: String,
+    AEMode: EdsUInt32,
+    DriveMode: EdsDriveMode,
+    ISOSpeed: EdsISO,
+    MeteringMode: EdsMeteringMode,
+    AFMode: EdsAFMode,
+    Av: EdsAv,
+    Tv: EdsTv,
+    ExposureCompensation: EdsExposureCompensation,
+    FocalLength: PropertyValue,
+    AvailableShots: EdsUInt32,
+    Bracket: EdsUInt32,
+    WhiteBalanceBracket: Vec<EdsInt32>,
+    LensName: String,
+    AEBracket: BracketSetting,
+    FEBracket: BracketSetting,
+    ISOBracket: BracketSetting,
+    NoiseReduction: EdsNoiseReduction,
+    FlashOn: EdsUInt32,
+    RedEye: EdsRedEye,
+    FlashMode: EdsFlashMode,
+    LensStatus: EdsUInt32,
+    Artist: String,
+    Copyright: String,
+    AEModeSelect: EdsUInt32,
+    PowerZoomSpeed: EdsUInt32,
+    ColorFilter: EdsUInt32,
+    DigitalZoomSetting: EdsUInt32,
+    AfLockState: EdsUInt32,
+    BrightnessSetting: EdsUInt32,
+    EvfOutputDevice: EdsEvfOutputDevice,
+    EvfMode: EdsEvfMode,
+    EvfWhiteBalance: EdsWhiteBalance,
+    EvfColorTemperature: EdsUInt32,
+    EvfDepthOfFieldPreview: EdsEvfDepthOfFieldPreview,
+    EvfZoom: EdsUInt32,
+    EvfZoomPosition: EdsPoint,
+    EvfHistogram: Vec<EdsUInt32>,
+    EvfImagePosition: EdsPoint,
+    EvfHistogramStatus: EdsUInt32,
+    EvfAfmode: EdsUInt32,
+    Record: EdsUInt32,
+    EvfHistogramY: Vec<EdsUInt32>,
+    EvfHistogramR: Vec<EdsUInt32>,
+    EvfHistogramG: Vec<EdsUInt32>,
+    EvfHistogramB: Vec<EdsUInt32>,
+    EvfCoordinateSystem: PropertyValue,
+    EvfZoomRect: EdsRect,
+    EvfImageClipRect: EdsRect,
+    EvfPowerZoomCurPosition: EdsUInt32,
+    EvfPowerZoomMaxPosition: EdsUInt32,
+    EvfPowerZoomMinPosition: EdsUInt32,
+    UTCTime: EdsTime,
+    TimeZone: EdsUInt32,
+    SummerTimeSetting: EdsUInt32,
+    ManualWhiteBalanceData: ManualWhiteBalance,
+    TempStatus: EdsUInt32,
+    MirrorLockUpState: EdsUInt32,
+    FixedMovie: EdsUInt32,
+    MovieParam: EdsUInt32,
+    Aspect: EdsUInt32,
+    ContinuousAfMode: EdsUInt32,
+    MirrorUpSetting: EdsUInt32,
+    MovieServoAf: EdsUInt32,
+    AutoPowerOffSetting: EdsUInt32,
+    AFEyeDetect: EdsUInt32,
+    FocusShiftSetting: PropertyValue,
+    MovieHFRSetting: EdsUInt32,
+    AFTrackingObject: EdsUInt32,
+    RegisterFocusEdge: EdsUInt32,
+    DriveFocusToEdge: EdsUInt32,
+    FocusPosition: EdsUInt32,
+    StillMovieDivideSetting: EdsUInt32,
+    CardExtension: EdsUInt32,
+    MovieCardExtension: EdsUInt32,
+    StillCurrentMedia: EdsUInt32,
+    MovieCurrentMedia: EdsUInt32,
+    ApertureLockSetting: PropertyValue,
+    LensIsSetting: EdsUInt32,
+    ScreenDimmerTime: EdsUInt32,
+    ScreenOffTime: EdsUInt32,
+    ViewfinderOffTime: EdsUInt32,
+    EvfClickWbcoeffs: PropertyValue,
+    EvfRollingPitching: PropertyValue,
+    EvfVisibleRect: EdsRect,
+    DcZoom: EdsUInt32,
+    DcStrobe: EdsUInt32,
+    LensBarrelStatus: EdsUInt32,
+);
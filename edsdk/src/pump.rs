@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+use crate::*;
+use std::{
+    cell::Cell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Held while `EdsGetEvent` runs, so that no event handler is in flight while
+/// its context is released or the SDK terminated.
+static EVENT_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+thread_local! {
+    /// Set while the current thread holds `EVENT_LOCK`, e.g. from the event handlers.
+    static HOLDS_EVENT_LOCK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` while no event handler is in flight.
+///
+/// From an event handler, `f` is simply called: the lock is already held by
+/// the current thread.
+pub(crate) fn with_event_lock<R>(f: impl FnOnce() -> R) -> R {
+    if HOLDS_EVENT_LOCK.get() {
+        return f();
+    }
+    let _lock = EVENT_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    HOLDS_EVENT_LOCK.set(true);
+    let res = f();
+    HOLDS_EVENT_LOCK.set(false);
+    res
+}
+
+/// Where the event pump started with the SDK runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventPumpMode {
+    /// No pump: the application has to call `eds_get_event` itself.
+    #[default]
+    Manual,
+    /// A task spawned on the current tokio runtime. `EdsGetEvent` blocks, so
+    /// it is called on the blocking threads of the runtime rather than on the
+    /// task's worker thread.
+    Task,
+    /// A dedicated thread.
+    Thread,
+    /// A dedicated thread, which also initializes and terminates the SDK. For
+    /// platforms only delivering events to the thread that initialized it.
+    InitThread,
+}
+
+/// Configuration of the event pump, see [`Sdk::with_event_pump`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventPumpConfig {
+    pub mode: EventPumpMode,
+    /// Delay between two calls to `EdsGetEvent`.
+    pub interval: Duration,
+}
+
+impl Default for EventPumpConfig {
+    fn default() -> Self {
+        Self {
+            mode: EventPumpMode::Task,
+            interval: Duration::from_millis(50),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Worker {
+    Task(tokio::task::JoinHandle<()>),
+    Thread(thread::JoinHandle<()>),
+}
+
+/// Running event pump, stopped when dropped.
+#[derive(Debug)]
+pub(crate) struct EventPump {
+    stop: Arc<AtomicBool>,
+    worker: Option<Worker>,
+}
+
+/// Calls `EdsGetEvent` unless the pump has been stopped.
+fn poll(stop: &AtomicBool) -> bool {
+    with_event_lock(|| {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        let _ = check_call!(EdsGetEvent());
+        true
+    })
+}
+
+impl EventPump {
+    /// Starts the pump on an initialized SDK. Returns `None` in manual mode.
+    ///
+    /// Use [`EventPump::initialize_on_thread`] for [`EventPumpMode::InitThread`].
+    pub(crate) fn start(config: &EventPumpConfig) -> Result<Option<Self>, Error> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let interval = config.interval;
+        let worker = match config.mode {
+            EventPumpMode::Manual => return Ok(None),
+            EventPumpMode::Task => {
+                let runtime = tokio::runtime::Handle::try_current()
+                    .map_err(|_| Error::new(EdsError::InvalidFnCall))?;
+                let stop = stop.clone();
+                Worker::Task(runtime.spawn(async move {
+                    let mut ticks = tokio::time::interval(interval);
+                    loop {
+                        ticks.tick().await;
+                        let stop = stop.clone();
+                        match tokio::task::spawn_blocking(move || poll(&stop)).await {
+                            Ok(true) => (),
+                            Ok(false) | Err(_) => break,
+                        }
+                    }
+                }))
+            }
+            EventPumpMode::Thread => {
+                let stop = stop.clone();
+                let thread = thread::Builder::new()
+                    .name("edsdk-event-pump".into())
+                    .spawn(move || {
+                        while poll(&stop) {
+                            thread::sleep(interval);
+                        }
+                    })
+                    .map_err(|_| Error::new(EdsError::InternalError))?;
+                Worker::Thread(thread)
+            }
+            EventPumpMode::InitThread => return Err(EdsError::InvalidFnCall.into()),
+        };
+        Ok(Some(Self {
+            stop,
+            worker: Some(worker),
+        }))
+    }
+
+    /// Starts a pump thread calling `EdsInitializeSDK` before polling, and
+    /// `EdsTerminateSDK` once stopped.
+    pub(crate) fn initialize_on_thread(interval: Duration) -> Result<Self, Error> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (initialized, result) = mpsc::channel();
+        let thread = {
+            let stop = stop.clone();
+            thread::Builder::new()
+                .name("edsdk-event-pump".into())
+                .spawn(move || {
+                    let res = check_call!(EdsInitializeSDK());
+                    let ok = res.is_ok();
+                    let _ = initialized.send(res);
+                    if !ok {
+                        return;
+                    }
+                    while poll(&stop) {
+                        thread::sleep(interval);
+                    }
+                    let _ = check_call!(EdsTerminateSDK());
+                })
+                .map_err(|_| Error::new(EdsError::InternalError))?
+        };
+        match result.recv() {
+            Ok(Ok(())) => Ok(Self {
+                stop,
+                worker: Some(Worker::Thread(thread)),
+            }),
+            Ok(Err(err)) => {
+                let _ = thread.join();
+                Err(err)
+            }
+            Err(_) => Err(EdsError::InternalError.into()),
+        }
+    }
+}
+
+impl Drop for EventPump {
+    fn drop(&mut self) {
+        // Once set under the lock, no `EdsGetEvent` call is in flight nor will
+        // be made by the pump.
+        with_event_lock(|| self.stop.store(true, Ordering::SeqCst));
+        match self.worker.take() {
+            Some(Worker::Task(task)) => task.abort(),
+            Some(Worker::Thread(thread)) if thread.thread().id() != thread::current().id() => {
+                let _ = thread.join();
+            }
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! Bit fields of the [`EdsImageQuality`] codes.
+//!
+//! A code packs up to two images, e.g. a RAW and a JPEG, each described by its
+//! size, type and compression:
+//!
+//! | bits  | image     | field       |
+//! |-------|-----------|-------------|
+//! | 31-24 | primary   | size        |
+//! | 23-20 | primary   | type        |
+//! | 19-16 | primary   | compression |
+//! | 15-8  | secondary | size        |
+//! | 7-4   | secondary | type        |
+//! | 3-0   | secondary | compression |
+//!
+//! A missing secondary image is encoded as size `0xFF`, type `0` and
+//! compression `0xF`.
+
+use crate::*;
+
+const NO_SECONDARY: EdsUInt32 = 0xFF0F;
+/// Compression field of the images without a compression setting, e.g. HEIF.
+const NO_COMPRESSION: EdsUInt32 = 0;
+
+/// Number of images saved with an image quality code. Only the secondary image
+/// is looked at, so that codes holding unknown values are counted as well.
+pub(crate) fn image_count(code: EdsUInt32) -> usize {
+    if code & 0xFFFF == NO_SECONDARY { 1 } else { 2 }
+}
+
+/// One of the images saved for a shot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageComponent {
+    pub image_type: EdsImageType,
+    pub size: EdsImageSize,
+    /// `None` for the formats without a compression setting.
+    pub compression: Option<EdsCompressQuality>,
+}
+
+impl ImageComponent {
+    pub fn new(
+        image_type: EdsImageType,
+        size: EdsImageSize,
+        compression: Option<EdsCompressQuality>,
+    ) -> Self {
+        Self {
+            image_type,
+            size,
+            compression,
+        }
+    }
+
+    /// Decodes the size, type and compression fields of a component.
+    fn decode(size: EdsUInt32, image_type: EdsUInt32, compression: EdsUInt32) -> Option<Self> {
+        Some(Self {
+            image_type: EdsImageType::try_from_primitive(image_type).ok()?,
+            size: EdsImageSize::try_from_primitive(size).ok()?,
+            compression: match compression {
+                NO_COMPRESSION => None,
+                compression => Some(EdsCompressQuality::try_from_primitive(compression).ok()?),
+            },
+        })
+    }
+
+    /// Returns the size, type and compression fields of the component, `None` if
+    /// one of them does not fit its field.
+    fn encode(&self) -> Option<(EdsUInt32, EdsUInt32, EdsUInt32)> {
+        let size = EdsUInt32::from(self.size);
+        let image_type = EdsUInt32::from(self.image_type);
+        let compression = self.compression.map_or(NO_COMPRESSION, EdsUInt32::from);
+        (size <= 0xFF && image_type <= 0xF && compression <= 0xF).then_some((
+            size,
+            image_type,
+            compression,
+        ))
+    }
+}
+
+/// Images saved for a shot, as packed in an [`EdsImageQuality`] code.
+///
+/// Also covers the codes missing from [`EdsImageQuality`], which can be read
+/// and written with [`CameraSession::get_quality_components`] and
+/// [`CameraSession::set_quality_components`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageQualityComponents {
+    pub primary: ImageComponent,
+    /// Second image saved along with the first one, e.g. the JPEG of RAW+JPEG.
+    pub secondary: Option<ImageComponent>,
+}
+
+impl ImageQualityComponents {
+    pub fn new(primary: ImageComponent, secondary: Option<ImageComponent>) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Decodes an image quality code, `None` if a field holds an unknown value.
+    pub fn from_code(code: EdsUInt32) -> Option<Self> {
+        let primary = ImageComponent::decode(code >> 24, (code >> 20) & 0xF, (code >> 16) & 0xF)?;
+        let secondary = match code & 0xFFFF {
+            NO_SECONDARY => None,
+            _ => Some(ImageComponent::decode(
+                (code >> 8) & 0xFF,
+                (code >> 4) & 0xF,
+                code & 0xF,
+            )?),
+        };
+        Some(Self { primary, secondary })
+    }
+
+    /// Image quality code, `None` if a component holds a value, such as
+    /// `EdsImageSize::Unknown`, which does not fit its field.
+    pub fn code(&self) -> Option<EdsUInt32> {
+        let (size, image_type, compression) = self.primary.encode()?;
+        let secondary = match self.secondary {
+            Some(secondary) => {
+                let (size, image_type, compression) = secondary.encode()?;
+                size << 8 | image_type << 4 | compression
+            }
+            None => NO_SECONDARY,
+        };
+        Some(size << 24 | image_type << 20 | compression << 16 | secondary)
+    }
+
+    /// Matching [`EdsImageQuality`], if the code is one of its variants.
+    pub fn quality(&self) -> Option<EdsImageQuality> {
+        EdsImageQuality::try_from_primitive(self.code()?).ok()
+    }
+}
+
+impl EdsImageQuality {
+    /// Images saved with this quality. `None` for [`EdsImageQuality::Unknown`].
+    pub fn components(self) -> Option<ImageQualityComponents> {
+        ImageQualityComponents::from_code(self.into())
+    }
+}
+
+impl PropertyType for ImageQualityComponents {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        Self::from_code(EdsUInt32::from_value(value)?)
+            .ok_or(Error::new(EdsError::PropertiesMismatch))
+    }
+
+    fn into_value(self) -> PropertyValue {
+        // A code that cannot be encoded is sent as unknown, for the camera to reject.
+        let code = self.code().unwrap_or(EdsImageQuality::Unknown.into());
+        PropertyValue::UInt32(code)
+    }
+}
+
+impl CameraSession {
+    /// Reads the image quality, including codes missing from [`EdsImageQuality`].
+    pub async fn get_quality_components(&self) -> Result<ImageQualityComponents, Error> {
+        PropertyType::from_value(self.get_property(EdsPropertyID::ImageQuality).await?)
+    }
+
+    /// Lists the image qualities the camera can be set to, including codes
+    /// missing from [`EdsImageQuality`].
+    pub async fn get_all_quality_components(&self) -> Result<Vec<ImageQualityComponents>, Error> {
+        Ok(describe_property(self, EdsPropertyID::ImageQuality)
+            .await?
+            .values)
+    }
+
+    pub async fn set_quality_components(&self, value: ImageQualityComponents) -> Result<(), Error> {
+        let code = value.code().ok_or(Error::new(EdsError::InvalidParameter))?;
+        self.set_property(EdsPropertyID::ImageQuality, &PropertyValue::UInt32(code))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUALITIES: &[EdsImageQuality] = {
+        use EdsImageQuality::*;
+        &[
+            LJ, MJ, M1J, M1F, M1N, M2J, M2F, M2N, SJ, S1J, S2J, LJF, LJN, MJF, MJN, SJF, SJN, S1JF,
+            S1JN, S2JF, S3JF, LR, LRLJF, LRLJN, LRMJF, LRMJN, LRSJF, LRSJN, LRS1JF, LRS1JN, LRS2JF,
+            LRS3JF, LRLJ, LRMJ, LRM1J, LRM1F, LRM1N, LRM2J, LRM2F, LRM2N, LRSJ, LRS1J, LRS2J, MR,
+            MRLJF, MRLJN, MRMJF, MRMJN, MRSJF, MRSJN, MRS1JF, MRS1JN, MRS2JF, MRS3JF, MRLJ, MRM1J,
+            MRM1F, MRM1N, MRM2J, MRM2F, MRM2N, MRSJ, SR, SRLJF, SRLJN, SRMJF, SRMJN, SRSJF, SRSJN,
+            SRS1JF, SRS1JN, SRS2JF, SRS3JF, SRLJ, SRM1J, SRM1F, SRM1N, SRM2J, SRM2F, SRM2N, SRSJ,
+            CR, CRLJF, CRMJF, CRM1JF, CRM2JF, CRSJF, CRS1JF, CRS2JF, CRS3JF, CRLJN, CRMJN, CRM1JN,
+            CRM2JN, CRSJN, CRS1JN, CRLJ, CRMJ, CRM1J, CRM2J, CRSJ, CRS1J, CRS2J, HEIFL, HEIFM,
+            HEIFM1, HEIFM2, HEIFLF, HEIFLN, HEIFMF, HEIFMN, HEIFS1, HEIFS1F, HEIFS1N, HEIFS2,
+            HEIFS2F, RHEIFL, RHEIFLF, RHEIFLN, RHEIFM, RHEIFM1, RHEIFM2, RHEIFMF, RHEIFMN, RHEIFS1,
+            RHEIFS1F, RHEIFS1N, RHEIFS2, RHEIFS2F, CRHEIFL, CRHEIFLF, CRHEIFLN, CRHEIFM, CRHEIFMF,
+            CRHEIFMN, CRHEIFM1, CRHEIFM2, CRHEIFS1, CRHEIFS1F, CRHEIFS1N, CRHEIFS2, CRHEIFS2F,
+        ]
+    };
+
+    #[test]
+    fn every_quality_round_trips() {
+        for &quality in QUALITIES {
+            let components = quality.components().expect("known quality");
+            assert_eq!(
+                components.code(),
+                Some(EdsUInt32::from(quality)),
+                "{quality:?}"
+            );
+            assert_eq!(components.quality(), Some(quality));
+        }
+        assert_eq!(EdsImageQuality::Unknown.components(), None);
+    }
+
+    #[test]
+    fn image_counts() {
+        assert_eq!(image_count(EdsImageQuality::LJ.into()), 1);
+        assert_eq!(image_count(EdsImageQuality::LR.into()), 1);
+        assert_eq!(image_count(EdsImageQuality::LRLJF.into()), 2);
+        // Unknown secondary size.
+        assert_eq!(image_count(0x0064_7012), 2);
+    }
+
+    #[test]
+    fn heif_has_no_compression() {
+        let components = EdsImageQuality::HEIFL.components().unwrap();
+        assert_eq!(
+            components,
+            ImageQualityComponents::new(
+                ImageComponent::new(EdsImageType::HEIF, EdsImageSize::Large, None),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn code_missing_from_the_enum() {
+        // RAW + S2 JPEG Normal.
+        let code = 0x0064_0F12;
+        let components = ImageQualityComponents::from_code(code).unwrap();
+        assert_eq!(
+            components,
+            ImageQualityComponents::new(
+                ImageComponent::new(
+                    EdsImageType::CR2,
+                    EdsImageSize::Large,
+                    Some(EdsCompressQuality::Lossless)
+                ),
+                Some(ImageComponent::new(
+                    EdsImageType::Jpeg,
+                    EdsImageSize::Small2,
+                    Some(EdsCompressQuality::Normal)
+                ))
+            )
+        );
+        assert_eq!(components.code(), Some(code));
+        assert!(components.quality().is_none());
+        assert_eq!(image_count(code), 2);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        // Primary image of type 3, which no format uses.
+        assert_eq!(ImageQualityComponents::from_code(0x0033_FF0F), None);
+        let unknown_size = ImageComponent::new(EdsImageType::Jpeg, EdsImageSize::Unknown, None);
+        assert_eq!(ImageQualityComponents::new(unknown_size, None).code(), None);
+    }
+}
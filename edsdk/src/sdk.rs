@@ -4,8 +4,9 @@
 include!("link.rs");
 use std::{
     any::{Any, TypeId},
+    collections::HashSet,
     ptr::null_mut,
-    sync::Arc,
+    sync::{Arc, Weak},
 };
 use tokio::sync::Mutex;
 
@@ -20,115 +21,371 @@ impl<T> EdsRefWrapper<T> {
 
 macro_rules! check_call {
     ($call:expr) => {{
-        let code = unsafe { $call };
-        match code {
+        let code: EdsErrorCode = unsafe { $call };
+        match EdsError::from_code(code) {
             EdsError::Ok => Ok(()),
-            err => {
-                println!(
-                    "Call to `{}` failed with code {:?}",
-                    stringify!($call),
-                    code
-                );
-                Err(err)
-            }
+            _ => Err(Error::with_operation(code, stringify!($call))),
         }
     }};
 }
 
-pub fn eds_initialize_sdk() -> Result<(), EdsError> {
-    check_call!(EdsInitializeSDK())
+/// Value returned by `EdsRetain` and `EdsRelease` when the reference is invalid.
+const EDS_INVALID_REF_COUNT: EdsUInt32 = 0xFFFFFFFF;
+
+/// State of the process-wide SDK initialization.
+struct SdkState {
+    context: Weak<SdkContext>,
+    /// Set from `EdsInitializeSDK` until `EdsTerminateSDK` has returned.
+    initialized: bool,
+}
+
+static SDK_STATE: std::sync::Mutex<SdkState> = std::sync::Mutex::new(SdkState {
+    context: Weak::new(),
+    initialized: false,
+});
+
+/// Shared by the [`Sdk`] guard and every handle: the SDK is terminated when
+/// the last of them is dropped, after every object has been released.
+#[derive(Debug)]
+struct SdkContext {
+    pump: Option<EventPump>,
+    /// `EdsTerminateSDK` is called by the pump thread.
+    terminated_by_pump: bool,
+    /// Addresses of the cameras with an open [`CameraSession`].
+    sessions: std::sync::Mutex<HashSet<usize>>,
 }
 
-pub fn eds_terminate_sdk() -> Result<(), EdsError> {
-    check_call!(EdsTerminateSDK())
+impl SdkContext {
+    fn current() -> Result<Arc<SdkContext>, Error> {
+        let state = SDK_STATE.lock().unwrap_or_else(|err| err.into_inner());
+        state
+            .context
+            .upgrade()
+            .ok_or(Error::new(EdsError::InvalidFnCall))
+    }
+
+    /// Marks `camera` as having an open session, failing with
+    /// `EdsError::SessionAlreadyOpen` if it already has one: a second session
+    /// would replace the event handlers of the first, and close it when dropped.
+    fn claim_session(&self, camera: EdsCameraRef) -> Result<(), Error> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+        if !sessions.insert(EdsBaseRef::from(camera).0 as usize) {
+            return Err(EdsError::SessionAlreadyOpen.into());
+        }
+        Ok(())
+    }
+
+    fn release_session(&self, camera: EdsCameraRef) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|err| err.into_inner());
+        sessions.remove(&(EdsBaseRef::from(camera).0 as usize));
+    }
 }
 
-pub async fn eds_release(in_ref: EdsRefWrapper<EdsBaseRef>) -> Result<(), EdsError> {
-    let ptr = in_ref.0.lock().await.0;
-    if !ptr.0.is_null() {
-        check_call!(EdsRelease(ptr))?;
+impl Drop for SdkContext {
+    fn drop(&mut self) {
+        // Stopping the pump takes `EVENT_LOCK`, which must not be waited for
+        // while holding `SDK_STATE`.
+        drop(self.pump.take());
+        let mut state = SDK_STATE.lock().unwrap_or_else(|err| err.into_inner());
+        if !self.terminated_by_pump {
+            let _ = check_call!(EdsTerminateSDK());
+        }
+        state.initialized = false;
+    }
+}
+
+/// Guard keeping the SDK initialized.
+///
+/// Only one guard can exist at a time in the process. Every handle obtained
+/// while it is alive keeps the SDK initialized as well, so dropping the guard
+/// before the cameras, streams or downloaded items only terminates the SDK
+/// once the last of them has been released.
+#[derive(Debug)]
+pub struct Sdk {
+    _context: Arc<SdkContext>,
+}
+
+impl Sdk {
+    /// Calls `EdsInitializeSDK`. Fails with `EdsError::InvalidFnCall` if the SDK
+    /// is already initialized, or is still being kept alive by some handles.
+    ///
+    /// Events are not polled: `eds_get_event` has to be called by the application.
+    pub fn initialize() -> Result<Sdk, Error> {
+        Self::with_event_pump(EventPumpConfig {
+            mode: EventPumpMode::Manual,
+            ..Default::default()
+        })
+    }
+
+    /// Initializes the SDK and starts an event pump calling `EdsGetEvent`, stopped
+    /// right before the SDK is terminated.
+    ///
+    /// [`EventPumpMode::Task`] fails with `EdsError::InvalidFnCall` outside of a
+    /// tokio runtime.
+    pub fn with_event_pump(config: EventPumpConfig) -> Result<Sdk, Error> {
+        let mut state = SDK_STATE.lock().unwrap_or_else(|err| err.into_inner());
+        if state.initialized {
+            return Err(EdsError::InvalidFnCall.into());
+        }
+        let context = if config.mode == EventPumpMode::InitThread {
+            SdkContext {
+                pump: Some(EventPump::initialize_on_thread(config.interval)?),
+                terminated_by_pump: true,
+                sessions: Default::default(),
+            }
+        } else {
+            check_call!(EdsInitializeSDK())?;
+            match EventPump::start(&config) {
+                Ok(pump) => SdkContext {
+                    pump,
+                    terminated_by_pump: false,
+                    sessions: Default::default(),
+                },
+                Err(err) => {
+                    let _ = check_call!(EdsTerminateSDK());
+                    return Err(err);
+                }
+            }
+        };
+        let context = Arc::new(context);
+        state.context = Arc::downgrade(&context);
+        state.initialized = true;
+        Ok(Sdk { _context: context })
+    }
+
+    pub fn camera_list(&self) -> Result<CameraList, Error> {
+        eds_get_camera_list()
+    }
+}
+
+/// Common interface of the owned SDK object handles.
+pub trait EdsObject {
+    /// Raw reference to the underlying SDK object. The handle keeps ownership of it.
+    fn as_base_ref(&self) -> EdsBaseRef;
+}
+
+/// SDK objects whose properties can be read and written with `EdsGetPropertySize`,
+/// `EdsGetPropertyData`, `EdsSetPropertyData` and `EdsGetPropertyDesc`.
+///
+/// The SDK only accepts cameras, images and live view images there.
+pub trait EdsPropertyHolder: EdsObject {}
+
+/// SDK objects that can report their progress through `EdsSetProgressCallback`.
+///
+/// The SDK only accepts streams and images there.
+pub trait EdsProgressSource: EdsObject {}
+
+/// SDK objects whose children can be enumerated with `EdsGetChildCount` and
+/// `EdsGetChildAtIndex`.
+pub trait EdsContainer: EdsObject {
+    type Child: EdsObject;
+
+    /// # Safety
+    /// `in_ref` must be a reference returned by the SDK for a child of this container.
+    unsafe fn child_from_raw(in_ref: EdsBaseRef) -> Self::Child;
+}
+
+/// SDK objects whose parent can be retrieved with `EdsGetParent`.
+pub trait EdsChild: EdsObject {
+    type Parent: EdsObject;
+
+    /// # Safety
+    /// `in_ref` must be a reference returned by the SDK for the parent of this object.
+    unsafe fn parent_from_raw(in_ref: EdsBaseRef) -> Self::Parent;
+}
+
+/// Declares an owned handle around an SDK reference.
+///
+/// The handle calls `EdsRelease` exactly once when dropped, and cloning it
+/// calls `EdsRetain` so that every clone owns its own reference count.
+macro_rules! eds_handle {
+    ($(#[$attr:meta])* $name:ident($raw:ty)) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name($raw, Arc<SdkContext>);
+
+        impl $name {
+            /// Takes ownership of `in_ref`, which is released when the handle is dropped.
+            ///
+            /// # Safety
+            /// `in_ref` must be a valid reference of the right kind, and the caller must
+            /// not release it by any other mean. The SDK must be initialized.
+            pub unsafe fn from_raw(in_ref: $raw) -> Self {
+                let sdk = SdkContext::current().expect("SDK object created without an initialized SDK");
+                Self::from_raw_in(in_ref, sdk)
+            }
+
+            fn from_raw_in(in_ref: $raw, sdk: Arc<SdkContext>) -> Self {
+                Self(in_ref, sdk)
+            }
+
+            /// Raw reference to the underlying SDK object. The handle keeps ownership of it.
+            pub fn as_raw(&self) -> $raw {
+                self.0
+            }
+        }
+
+        impl EdsObject for $name {
+            fn as_base_ref(&self) -> EdsBaseRef {
+                self.0.into()
+            }
+        }
+
+        impl Clone for $name {
+            fn clone(&self) -> Self {
+                if !self.0.is_null() {
+                    let count = unsafe { EdsRetain(self.0.into()) };
+                    debug_assert_ne!(count, EDS_INVALID_REF_COUNT, "EdsRetain on invalid reference");
+                }
+                Self(self.0, self.1.clone())
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if !self.0.is_null() {
+                    let count = unsafe { EdsRelease(self.0.into()) };
+                    debug_assert_ne!(count, EDS_INVALID_REF_COUNT, "EdsRelease on invalid reference");
+                }
+            }
+        }
     };
-    Ok(())
 }
 
-pub async fn eds_retain(in_ref: EdsRefWrapper<EdsBaseRef>) -> Result<(), EdsError> {
-    check_call!(EdsRetain(*in_ref.0.lock().await))
+eds_handle!(
+    /// List of the cameras connected to the host, see [`eds_get_camera_list`].
+    CameraList(EdsCameraListRef)
+);
+eds_handle!(
+    /// A camera, obtained from a [`CameraList`].
+    Camera(EdsCameraRef)
+);
+eds_handle!(
+    /// A storage volume (memory card) of a [`Camera`].
+    Volume(EdsVolumeRef)
+);
+eds_handle!(
+    /// A file or folder stored on a [`Volume`].
+    DirectoryItem(EdsDirectoryItemRef)
+);
+eds_handle!(
+    /// A file or memory stream.
+    Stream(EdsStreamRef)
+);
+eds_handle!(
+    /// An image decoded from a [`Stream`].
+    Image(EdsImageRef)
+);
+eds_handle!(
+    /// A live view image, see [`eds_download_evf_image`].
+    EvfImage(EdsEvfImageRef)
+);
+
+impl EdsPropertyHolder for Image {}
+impl EdsPropertyHolder for EvfImage {}
+impl EdsProgressSource for Stream {}
+impl EdsProgressSource for Image {}
+
+impl EdsContainer for CameraList {
+    type Child = Camera;
+    unsafe fn child_from_raw(in_ref: EdsBaseRef) -> Camera {
+        unsafe { Camera::from_raw(EdsCameraRef::from_base(in_ref)) }
+    }
+}
+impl EdsContainer for Camera {
+    type Child = Volume;
+    unsafe fn child_from_raw(in_ref: EdsBaseRef) -> Volume {
+        unsafe { Volume::from_raw(EdsVolumeRef::from_base(in_ref)) }
+    }
+}
+impl EdsContainer for Volume {
+    type Child = DirectoryItem;
+    unsafe fn child_from_raw(in_ref: EdsBaseRef) -> DirectoryItem {
+        unsafe { DirectoryItem::from_raw(EdsDirectoryItemRef::from_base(in_ref)) }
+    }
+}
+impl EdsContainer for DirectoryItem {
+    type Child = DirectoryItem;
+    unsafe fn child_from_raw(in_ref: EdsBaseRef) -> DirectoryItem {
+        unsafe { DirectoryItem::from_raw(EdsDirectoryItemRef::from_base(in_ref)) }
+    }
+}
+impl EdsChild for Volume {
+    type Parent = Camera;
+    unsafe fn parent_from_raw(in_ref: EdsBaseRef) -> Camera {
+        unsafe { Camera::from_raw(EdsCameraRef::from_base(in_ref)) }
+    }
 }
 
-pub fn eds_get_camera_list() -> Result<EdsRefWrapper<EdsCameraListRef>, EdsError> {
-    let mut out_camera_list_ref = EdsBaseRef::new();
+pub fn eds_get_camera_list() -> Result<CameraList, Error> {
+    let sdk = SdkContext::current()?;
+    let mut out_camera_list_ref = EdsCameraListRef::new();
     check_call!(EdsGetCameraList(&mut out_camera_list_ref))?;
-    Ok(EdsRefWrapper::new(out_camera_list_ref))
+    Ok(CameraList::from_raw_in(out_camera_list_ref, sdk))
 }
 
-pub async fn eds_get_child_count(
-    in_ref: EdsRefWrapper<EdsCameraListRef>,
-) -> Result<EdsUInt32, EdsError> {
+pub async fn eds_get_child_count<T: EdsContainer>(in_ref: &T) -> Result<EdsUInt32, Error> {
     let mut out_count = EdsUInt32::default();
-    check_call!(EdsGetChildCount(*in_ref.0.lock().await, &mut out_count))?;
+    check_call!(EdsGetChildCount(in_ref.as_base_ref(), &mut out_count))?;
     Ok(out_count)
 }
 
-pub async fn eds_get_child_at_index(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+pub async fn eds_get_child_at_index<T: EdsContainer>(
+    in_ref: &T,
     in_index: EdsInt32,
-) -> Result<EdsRefWrapper<EdsBaseRef>, EdsError> {
+) -> Result<T::Child, Error> {
     let mut out_ref = EdsBaseRef::new();
     check_call!(EdsGetChildAtIndex(
-        *in_ref.0.lock().await,
+        in_ref.as_base_ref(),
         in_index,
         &mut out_ref
     ))?;
-    Ok(EdsRefWrapper::new(out_ref))
+    Ok(unsafe { T::child_from_raw(out_ref) })
 }
 
-pub async fn eds_get_device_info(
-    in_camera_ref: EdsRefWrapper<EdsBaseRef>,
-) -> Result<EdsDeviceInfo, EdsError> {
+pub async fn eds_get_device_info(in_camera_ref: &Camera) -> Result<EdsDeviceInfo, Error> {
     let mut out_device_info = EdsDeviceInfo::default();
     check_call!(EdsGetDeviceInfo(
-        *in_camera_ref.0.lock().await,
+        in_camera_ref.as_raw(),
         &mut out_device_info
     ))?;
     Ok(out_device_info)
 }
 
-pub async fn eds_get_parent(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
-) -> Result<EdsRefWrapper<EdsBaseRef>, EdsError> {
+pub async fn eds_get_parent<T: EdsChild>(in_ref: &T) -> Result<T::Parent, Error> {
     let mut out_parent_ref = EdsBaseRef::new();
-    check_call!(EdsGetParent(*in_ref.0.lock().await, &mut out_parent_ref))?;
-    Ok(EdsRefWrapper::new(out_parent_ref))
+    check_call!(EdsGetParent(in_ref.as_base_ref(), &mut out_parent_ref))?;
+    Ok(unsafe { T::parent_from_raw(out_parent_ref) })
 }
 
-pub async fn eds_get_property_size(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+pub async fn eds_get_property_size<T: EdsPropertyHolder>(
+    in_ref: &T,
     in_property_id: EdsPropertyID,
     in_param: EdsInt32,
-) -> Result<(EdsDataType, EdsUInt32), EdsError> {
-    let mut out_data_type = EdsDataType::default();
+) -> Result<(EdsDataType, EdsUInt32), Error> {
+    let mut out_data_type = EdsUInt32::default();
     let mut out_size = EdsUInt32::default();
     check_call!(EdsGetPropertySize(
-        *in_ref.0.lock().await,
-        in_property_id,
+        in_ref.as_base_ref(),
+        in_property_id.into(),
         in_param,
         &mut out_data_type,
         &mut out_size,
     ))?;
-    Ok((out_data_type, out_size))
+    Ok((EdsDataType::from(out_data_type), out_size))
 }
 
-pub async fn eds_get_property_data<T: Default>(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+pub async fn eds_get_property_data<T: Default, U: EdsPropertyHolder>(
+    in_ref: &U,
     in_property_id: EdsPropertyID,
     in_param: EdsInt32,
     in_property_size: EdsUInt32,
-) -> Result<T, EdsError> {
+) -> Result<T, Error> {
     let mut out_property_data = T::default();
     let ptr = &mut out_property_data as *mut T as *mut EdsVoid;
     check_call!(EdsGetPropertyData(
-        *in_ref.0.lock().await,
-        in_property_id,
+        in_ref.as_base_ref(),
+        in_property_id.into(),
         in_param,
         in_property_size,
         ptr,
@@ -136,167 +393,164 @@ pub async fn eds_get_property_data<T: Default>(
     Ok(out_property_data)
 }
 
-pub async fn eds_set_property_data(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+pub async fn eds_set_property_data<T: EdsPropertyHolder>(
+    in_ref: &T,
     in_property_id: EdsPropertyID,
     in_param: EdsInt32,
-    in_property_size: EdsUInt32,
-    in_property_data: *const EdsVoid,
-) -> Result<(), EdsError> {
+    in_property_data: &[u8],
+) -> Result<(), Error> {
     check_call!(EdsSetPropertyData(
-        *in_ref.0.lock().await,
-        in_property_id,
+        in_ref.as_base_ref(),
+        in_property_id.into(),
         in_param,
-        in_property_size,
-        in_property_data,
+        in_property_data.len() as EdsUInt32,
+        in_property_data.as_ptr() as *const EdsVoid,
     ))
 }
 
-pub async fn eds_get_property_desc(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+pub async fn eds_get_property_desc<T: EdsPropertyHolder>(
+    in_ref: &T,
     in_property_id: EdsPropertyID,
-) -> Result<EdsPropertyDesc, EdsError> {
+) -> Result<EdsPropertyDesc, Error> {
     let mut out_property_desc = EdsPropertyDesc::default();
     check_call!(EdsGetPropertyDesc(
-        *in_ref.0.lock().await,
-        in_property_id,
+        in_ref.as_base_ref(),
+        in_property_id.into(),
         &mut out_property_desc
     ))?;
     Ok(out_property_desc)
 }
 
-pub async fn eds_open_session(in_camera_ref: EdsRefWrapper<EdsCameraRef>) -> Result<(), EdsError> {
-    check_call!(EdsOpenSession(*in_camera_ref.0.lock().await))
+async fn eds_open_session(in_camera_ref: &Camera) -> Result<(), Error> {
+    check_call!(EdsOpenSession(in_camera_ref.as_raw()))
 }
 
-pub async fn eds_close_session(in_camera_ref: EdsRefWrapper<EdsCameraRef>) -> Result<(), EdsError> {
-    check_call!(EdsCloseSession(*in_camera_ref.0.lock().await))
+async fn eds_close_session(in_camera_ref: &Camera) -> Result<(), Error> {
+    check_call!(EdsCloseSession(in_camera_ref.as_raw()))
 }
 
 pub async fn eds_send_command<T>(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_command: EdsCameraCommand,
     in_param: T,
-) -> Result<(), EdsError>
+) -> Result<(), Error>
 where
     T: Into<i32>,
 {
     check_call!(EdsSendCommand(
-        *in_camera_ref.0.lock().await,
+        in_camera_ref.as_raw(),
         in_command,
         in_param.into()
     ))
 }
 
 pub async fn eds_send_status_command(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_status_command: EdsCameraStatusCommand,
     in_param: EdsInt32,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSendStatusCommand(
-        *in_camera_ref.0.lock().await,
+        in_camera_ref.as_raw(),
         in_status_command,
         in_param
     ))
 }
 
 pub async fn eds_set_capacity(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_capacity: EdsCapacity,
-) -> Result<(), EdsError> {
-    check_call!(EdsSetCapacity(*in_camera_ref.0.lock().await, in_capacity))
+) -> Result<(), Error> {
+    check_call!(EdsSetCapacity(in_camera_ref.as_raw(), in_capacity))
 }
 
-pub fn eds_get_volume_info(in_volume_ref: EdsVolumeRef) -> Result<EdsVolumeInfo, EdsError> {
+pub fn eds_get_volume_info(in_volume_ref: &Volume) -> Result<EdsVolumeInfo, Error> {
     let mut out_volume_info = EdsVolumeInfo::default();
-    check_call!(EdsGetVolumeInfo(in_volume_ref, &mut out_volume_info))?;
+    check_call!(EdsGetVolumeInfo(
+        in_volume_ref.as_raw(),
+        &mut out_volume_info
+    ))?;
     Ok(out_volume_info)
 }
 
-pub fn eds_format_volume(in_volume_ref: EdsVolumeRef) -> Result<(), EdsError> {
-    check_call!(EdsFormatVolume(in_volume_ref))
+pub fn eds_format_volume(in_volume_ref: &Volume) -> Result<(), Error> {
+    check_call!(EdsFormatVolume(in_volume_ref.as_raw()))
 }
 
 pub async fn eds_get_directory_item_info(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
-) -> Result<EdsDirectoryItemInfo, EdsError> {
+    in_dir_item_ref: &DirectoryItem,
+) -> Result<EdsDirectoryItemInfo, Error> {
     let mut out_dir_item_info = EdsDirectoryItemInfo::default();
     check_call!(EdsGetDirectoryItemInfo(
-        *in_dir_item_ref.0.lock().await,
+        in_dir_item_ref.as_raw(),
         &mut out_dir_item_info
     ))?;
     Ok(out_dir_item_info)
 }
 
-pub async fn eds_delete_directory_item(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
-) -> Result<(), EdsError> {
-    check_call!(EdsDeleteDirectoryItem(*in_dir_item_ref.0.lock().await))
+/// Deletes the item from the camera. On success the SDK releases the reference
+/// itself, so the handle is consumed without calling `EdsRelease`.
+pub async fn eds_delete_directory_item(in_dir_item_ref: DirectoryItem) -> Result<(), Error> {
+    check_call!(EdsDeleteDirectoryItem(in_dir_item_ref.as_raw()))?;
+    std::mem::forget(in_dir_item_ref);
+    Ok(())
 }
 
 pub async fn eds_download(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
+    in_dir_item_ref: &DirectoryItem,
     in_read_size: EdsUInt64,
-    out_stream: EdsRefWrapper<EdsStreamRef>,
-) -> Result<(), EdsError> {
+    out_stream: &Stream,
+) -> Result<(), Error> {
     check_call!(EdsDownload(
-        *in_dir_item_ref.0.lock().await,
+        in_dir_item_ref.as_raw(),
         in_read_size,
-        *out_stream.0.lock().await
+        out_stream.as_raw()
     ))
 }
 
-pub async fn eds_download_cancel(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
-) -> Result<(), EdsError> {
-    check_call!(EdsDownloadCancel(*in_dir_item_ref.0.lock().await))
+pub async fn eds_download_cancel(in_dir_item_ref: &DirectoryItem) -> Result<(), Error> {
+    check_call!(EdsDownloadCancel(in_dir_item_ref.as_raw()))
 }
 
-pub async fn eds_download_complete(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
-) -> Result<(), EdsError> {
-    check_call!(EdsDownloadComplete(*in_dir_item_ref.0.lock().await))
+pub async fn eds_download_complete(in_dir_item_ref: &DirectoryItem) -> Result<(), Error> {
+    check_call!(EdsDownloadComplete(in_dir_item_ref.as_raw()))
 }
 
 pub async fn eds_download_thumbnail(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
-    out_stream: EdsRefWrapper<EdsStreamRef>,
-) -> Result<(), EdsError> {
+    in_dir_item_ref: &DirectoryItem,
+    out_stream: &Stream,
+) -> Result<(), Error> {
     check_call!(EdsDownloadThumbnail(
-        *in_dir_item_ref.0.lock().await,
-        *out_stream.0.lock().await
+        in_dir_item_ref.as_raw(),
+        out_stream.as_raw()
     ))
 }
 
 pub async fn eds_get_attribute(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
-) -> Result<EdsFileAttributes, EdsError> {
+    in_dir_item_ref: &DirectoryItem,
+) -> Result<EdsFileAttributes, Error> {
     let mut out_file_attribute = EdsFileAttributes::default();
     check_call!(EdsGetAttribute(
-        *in_dir_item_ref.0.lock().await,
+        in_dir_item_ref.as_raw(),
         &mut out_file_attribute
     ))?;
     Ok(out_file_attribute)
 }
 
 pub async fn eds_set_attribute(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
+    in_dir_item_ref: &DirectoryItem,
     in_file_attribute: EdsFileAttributes,
-) -> Result<(), EdsError> {
-    check_call!(EdsSetAttribute(
-        *in_dir_item_ref.0.lock().await,
-        in_file_attribute
-    ))
+) -> Result<(), Error> {
+    check_call!(EdsSetAttribute(in_dir_item_ref.as_raw(), in_file_attribute))
 }
 
 pub async fn eds_set_meta_image(
-    in_dir_item_ref: EdsRefWrapper<EdsDirectoryItemRef>,
+    in_dir_item_ref: &DirectoryItem,
     in_meta_type: EdsUInt32,
     in_meta_data_size: EdsUInt32,
     in_meta_data: *const EdsVoid,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSetMetaImage(
-        *in_dir_item_ref.0.lock().await,
+        in_dir_item_ref.as_raw(),
         in_meta_type,
         in_meta_data_size,
         in_meta_data,
@@ -309,23 +563,23 @@ pub unsafe fn eds_create_file_stream(
     in_file_name: *const EdsChar,
     in_create_disposition: EdsFileCreateDisposition,
     in_desired_access: EdsAccess,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let mut out_stream = EdsBaseRef::new();
+) -> Result<Stream, Error> {
+    let sdk = SdkContext::current()?;
+    let mut out_stream = EdsStreamRef::new();
     check_call!(EdsCreateFileStream(
         in_file_name,
         in_create_disposition,
         in_desired_access,
         &mut out_stream,
     ))?;
-    Ok(EdsRefWrapper::new(out_stream))
+    Ok(Stream::from_raw_in(out_stream, sdk))
 }
 
-pub fn eds_create_memory_stream(
-    in_buffer_size: EdsUInt64,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let mut out_stream = EdsBaseRef::new();
+pub fn eds_create_memory_stream(in_buffer_size: EdsUInt64) -> Result<Stream, Error> {
+    let sdk = SdkContext::current()?;
+    let mut out_stream = EdsStreamRef::new();
     check_call!(EdsCreateMemoryStream(in_buffer_size, &mut out_stream))?;
-    Ok(EdsRefWrapper::new(out_stream))
+    Ok(Stream::from_raw_in(out_stream, sdk))
 }
 
 /// # Safety
@@ -334,49 +588,49 @@ pub unsafe fn eds_create_file_stream_ex(
     in_file_name: *const EdsChar,
     in_create_disposition: EdsFileCreateDisposition,
     in_desired_access: EdsAccess,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let mut out_stream = EdsBaseRef::new();
+) -> Result<Stream, Error> {
+    let sdk = SdkContext::current()?;
+    let mut out_stream = EdsStreamRef::new();
     check_call!(EdsCreateFileStreamEx(
         in_file_name,
         in_create_disposition,
         in_desired_access,
         &mut out_stream,
     ))?;
-    Ok(EdsRefWrapper::new(out_stream))
+    Ok(Stream::from_raw_in(out_stream, sdk))
 }
 
 /// # Safety
-///
-/// sklksdf
+/// `in_user_buffer` must point to at least `in_buffer_size` bytes that stay
+/// valid for as long as the returned stream is alive.
 pub unsafe fn eds_create_memory_stream_from_pointer(
     in_user_buffer: *mut EdsVoid,
     in_buffer_size: EdsUInt64,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let mut out_stream = EdsBaseRef::new();
+) -> Result<Stream, Error> {
+    let sdk = SdkContext::current()?;
+    let mut out_stream = EdsStreamRef::new();
     check_call!(EdsCreateMemoryStreamFromPointer(
         in_user_buffer,
         in_buffer_size,
         &mut out_stream
     ))?;
-    Ok(EdsRefWrapper::new(out_stream))
+    Ok(Stream::from_raw_in(out_stream, sdk))
 }
 
-pub async fn eds_get_pointer(
-    in_stream: EdsRefWrapper<EdsStreamRef>,
-) -> Result<EdsRefWrapper<EdsBaseRef>, EdsError> {
+pub async fn eds_get_pointer(in_stream: &Stream) -> Result<EdsRefWrapper<EdsBaseRef>, Error> {
     let mut out_pointer = EdsBaseRef::new();
-    check_call!(EdsGetPointer(*in_stream.0.lock().await, &mut out_pointer))?;
+    check_call!(EdsGetPointer(in_stream.as_raw(), &mut out_pointer))?;
     Ok(EdsRefWrapper::new(out_pointer))
 }
 
 pub async fn eds_read(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
+    in_stream_ref: &Stream,
     in_read_size: EdsUInt64,
-) -> Result<(EdsUInt64, *mut EdsVoid), EdsError> {
+) -> Result<(EdsUInt64, *mut EdsVoid), Error> {
     let out_buffer = null_mut();
     let mut out_read_size = EdsUInt64::default();
     check_call!(EdsRead(
-        *in_stream_ref.0.lock().await,
+        in_stream_ref.as_raw(),
         in_read_size,
         out_buffer,
         &mut out_read_size
@@ -385,13 +639,13 @@ pub async fn eds_read(
 }
 
 pub async fn eds_write(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
+    in_stream_ref: &Stream,
     in_write_size: EdsUInt64,
     in_buffer: *const EdsVoid,
-) -> Result<EdsUInt64, EdsError> {
+) -> Result<EdsUInt64, Error> {
     let mut out_written_size = EdsUInt64::default();
     check_call!(EdsWrite(
-        *in_stream_ref.0.lock().await,
+        in_stream_ref.as_raw(),
         in_write_size,
         in_buffer,
         &mut out_written_size,
@@ -400,81 +654,71 @@ pub async fn eds_write(
 }
 
 pub async fn eds_seek(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
+    in_stream_ref: &Stream,
     in_seek_offset: EdsInt64,
     in_seek_origin: EdsSeekOrigin,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSeek(
-        *in_stream_ref.0.lock().await,
+        in_stream_ref.as_raw(),
         in_seek_offset,
         in_seek_origin
     ))
 }
 
-pub async fn eds_get_position(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
-) -> Result<EdsUInt64, EdsError> {
+pub async fn eds_get_position(in_stream_ref: &Stream) -> Result<EdsUInt64, Error> {
     let mut out_position = EdsUInt64::default();
-    check_call!(EdsGetPosition(
-        *in_stream_ref.0.lock().await,
-        &mut out_position
-    ))?;
+    check_call!(EdsGetPosition(in_stream_ref.as_raw(), &mut out_position))?;
     Ok(out_position)
 }
 
-pub async fn eds_get_length(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
-) -> Result<EdsUInt64, EdsError> {
+pub async fn eds_get_length(in_stream_ref: &Stream) -> Result<EdsUInt64, Error> {
     let mut out_length = EdsUInt64::default();
-    check_call!(EdsGetLength(*in_stream_ref.0.lock().await, &mut out_length))?;
+    check_call!(EdsGetLength(in_stream_ref.as_raw(), &mut out_length))?;
     Ok(out_length)
 }
 
 pub async fn eds_copy_data(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
+    in_stream_ref: &Stream,
     in_write_size: EdsUInt64,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let out_stream = EdsBaseRef::new();
+    out_stream_ref: &Stream,
+) -> Result<(), Error> {
     check_call!(EdsCopyData(
-        *in_stream_ref.0.lock().await,
+        in_stream_ref.as_raw(),
         in_write_size,
-        out_stream
-    ))?;
-    Ok(EdsRefWrapper::new(out_stream))
+        out_stream_ref.as_raw()
+    ))
 }
 
-pub async fn eds_set_progress_callback(
-    in_ref: EdsRefWrapper<EdsBaseRef>,
+pub async fn eds_set_progress_callback<T: EdsProgressSource>(
+    in_ref: &T,
     in_progress_callback: EdsProgressCallback,
     in_progress_option: EdsProgressOption,
-    in_context: EdsRefWrapper<*mut EdsVoid>,
-) -> Result<(), EdsError> {
+    in_context: *mut EdsVoid,
+) -> Result<(), Error> {
     check_call!(EdsSetProgressCallback(
-        *in_ref.0.lock().await,
+        in_ref.as_base_ref(),
         in_progress_callback,
         in_progress_option,
-        *in_context.0.lock().await
+        in_context
     ))
 }
 
-pub async fn eds_create_image_ref(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
-) -> Result<EdsImageRef, EdsError> {
-    let mut out_image_ref = EdsBaseRef::new();
+pub async fn eds_create_image_ref(in_stream_ref: &Stream) -> Result<Image, Error> {
+    let mut out_image_ref = EdsImageRef::new();
     check_call!(EdsCreateImageRef(
-        *in_stream_ref.0.lock().await,
+        in_stream_ref.as_raw(),
         &mut out_image_ref
     ))?;
-    Ok(out_image_ref)
+    Ok(Image::from_raw_in(out_image_ref, in_stream_ref.1.clone()))
 }
 
 pub fn eds_get_image_info(
-    in_image_ref: EdsImageRef,
+    in_image_ref: &Image,
     in_image_source: EdsImageSource,
-) -> Result<EdsImageInfo, EdsError> {
+) -> Result<EdsImageInfo, Error> {
     let mut out_image_info = EdsImageInfo::default();
     check_call!(EdsGetImageInfo(
-        in_image_ref,
+        in_image_ref.as_raw(),
         in_image_source,
         &mut out_image_info
     ))?;
@@ -482,44 +726,42 @@ pub fn eds_get_image_info(
 }
 
 pub fn eds_get_image(
-    in_image_ref: EdsImageRef,
+    in_image_ref: &Image,
     in_image_source: EdsImageSource,
     in_image_type: EdsTargetImageType,
     in_src_rect: EdsRect,
     in_dst_size: EdsSize,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let out_stream = EdsBaseRef::new();
+    out_stream: &Stream,
+) -> Result<(), Error> {
     check_call!(EdsGetImage(
-        in_image_ref,
+        in_image_ref.as_raw(),
         in_image_source,
         in_image_type,
         in_src_rect,
         in_dst_size,
-        out_stream,
-    ))?;
-    Ok(EdsRefWrapper::new(out_stream))
+        out_stream.as_raw(),
+    ))
 }
 
-/// # Safety
-/// See the module-level documentation for safety requirements and pointer usage rules.
-pub async unsafe fn eds_create_evf_image_ref(
-    in_stream_ref: EdsRefWrapper<EdsStreamRef>,
-) -> Result<EdsRefWrapper<EdsEvfImageRef>, EdsError> {
-    let mut out_evf_image_ref = EdsBaseRef::new();
+pub async fn eds_create_evf_image_ref(in_stream_ref: &Stream) -> Result<EvfImage, Error> {
+    let mut out_evf_image_ref = EdsEvfImageRef::new();
     check_call!(EdsCreateEvfImageRef(
-        *in_stream_ref.0.lock().await,
+        in_stream_ref.as_raw(),
         &mut out_evf_image_ref
     ))?;
-    Ok(EdsRefWrapper::new(out_evf_image_ref))
+    Ok(EvfImage::from_raw_in(
+        out_evf_image_ref,
+        in_stream_ref.1.clone(),
+    ))
 }
 
 pub async fn eds_download_evf_image(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
-    in_evf_image_ref: EdsRefWrapper<EdsEvfImageRef>,
-) -> Result<(), EdsError> {
+    in_camera_ref: &CameraSession,
+    in_evf_image_ref: &EvfImage,
+) -> Result<(), Error> {
     check_call!(EdsDownloadEvfImage(
-        *in_camera_ref.0.lock().await,
-        *in_evf_image_ref.0.lock().await
+        in_camera_ref.as_raw(),
+        in_evf_image_ref.as_raw()
     ))
 }
 
@@ -528,7 +770,7 @@ pub async fn eds_download_evf_image(
 pub unsafe fn eds_set_camera_added_handler(
     in_camera_added_handler: EdsCameraAddedHandler,
     in_context: *mut EdsVoid,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSetCameraAddedHandler(
         in_camera_added_handler,
         in_context
@@ -536,42 +778,42 @@ pub unsafe fn eds_set_camera_added_handler(
 }
 
 pub async fn eds_set_property_event_handler(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_evnet: EdsPropertyEvent,
     in_property_event_handler: EdsPropertyEventHandler,
     in_context: *mut EdsVoid,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSetPropertyEventHandler(
-        *in_camera_ref.0.lock().await,
-        in_evnet,
+        in_camera_ref.as_raw(),
+        in_evnet.into(),
         in_property_event_handler,
         in_context,
     ))
 }
 
 pub async fn eds_set_object_event_handler(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_evnet: EdsObjectEvent,
     in_object_event_handler: EdsObjectEventHandler,
     in_context: *mut EdsVoid,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSetObjectEventHandler(
-        *in_camera_ref.0.lock().await,
-        in_evnet,
+        in_camera_ref.as_raw(),
+        in_evnet.into(),
         in_object_event_handler,
         in_context
     ))
 }
 
 pub async fn eds_set_camera_state_event_handler(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_evnet: EdsStateEvent,
     in_state_event_handler: EdsStateEventHandler,
     in_context: *mut EdsVoid,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSetCameraStateEventHandler(
-        *in_camera_ref.0.lock().await,
-        in_evnet,
+        in_camera_ref.as_raw(),
+        in_evnet.into(),
         in_state_event_handler,
         in_context
     ))
@@ -579,25 +821,24 @@ pub async fn eds_set_camera_state_event_handler(
 
 /// # Safety
 /// See the module-level documentation for safety requirements and pointer usage rules.
-pub unsafe fn eds_create_stream(
-    in_stream: *mut EdsIStream,
-) -> Result<EdsRefWrapper<EdsStreamRef>, EdsError> {
-    let mut out_stream = EdsBaseRef::new();
+pub unsafe fn eds_create_stream(in_stream: *mut EdsIStream) -> Result<Stream, Error> {
+    let sdk = SdkContext::current()?;
+    let mut out_stream = EdsStreamRef::new();
     check_call!(EdsCreateStream(in_stream, &mut out_stream))?;
-    Ok(EdsRefWrapper::new(out_stream))
+    Ok(Stream::from_raw_in(out_stream, sdk))
 }
 
-pub fn eds_get_event() -> Result<(), EdsError> {
-    check_call!(EdsGetEvent())
+pub fn eds_get_event() -> Result<(), Error> {
+    with_event_lock(|| check_call!(EdsGetEvent()))
 }
 
 pub async fn eds_set_frame_point(
-    in_camera_ref: EdsRefWrapper<EdsCameraRef>,
+    in_camera_ref: &CameraSession,
     in_framepoint: EdsPoint,
     in_lock_af_frame: EdsBool,
-) -> Result<(), EdsError> {
+) -> Result<(), Error> {
     check_call!(EdsSetFramePoint(
-        *in_camera_ref.0.lock().await,
+        in_camera_ref.as_raw(),
         in_framepoint,
         in_lock_af_frame
     ))
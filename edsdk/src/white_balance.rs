@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 Antoine Lanco
+
+//! Color temperature, white balance shift and manual white balance data.
+
+use crate::*;
+use std::ops::RangeInclusive;
+
+/// Color temperatures accepted by cameras not describing the property, in Kelvin.
+pub const COLOR_TEMPERATURE_RANGE: RangeInclusive<EdsUInt32> = 2500..=10000;
+/// Step between two color temperatures, in Kelvin.
+pub const COLOR_TEMPERATURE_STEP: EdsUInt32 = 100;
+/// Bounds of each axis of a [`WbShift`].
+pub const WB_SHIFT_RANGE: RangeInclusive<EdsInt32> = -9..=9;
+
+/// Length of the caption of [`ManualWhiteBalance`], NUL included.
+const MANUAL_WB_CAPTION: usize = 32;
+/// Size of the `valid` and `dataSize` fields and of the caption of `EdsManualWBData`.
+const MANUAL_WB_HEADER: usize = 8 + MANUAL_WB_CAPTION;
+
+/// White balance shift, in steps along each axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WbShift {
+    /// Blue-amber axis.
+    pub ab: EdsInt32,
+    /// Green-magenta axis.
+    pub gm: EdsInt32,
+}
+
+impl PropertyType for WbShift {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        match Vec::<EdsInt32>::from_value(value)?[..] {
+            [ab, gm, ..] => Ok(Self { ab, gm }),
+            _ => Err(EdsError::PropertiesMismatch.into()),
+        }
+    }
+
+    fn into_value(self) -> PropertyValue {
+        PropertyValue::Int32Array(vec![self.ab, self.gm])
+    }
+}
+
+/// White balance measured by the camera, as laid out by `EdsManualWBData`.
+///
+/// `data` is opaque: it is only meant to be written back, e.g. to another
+/// camera of the same model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManualWhiteBalance {
+    pub valid: bool,
+    pub caption: String,
+    pub data: Vec<u8>,
+}
+
+impl ManualWhiteBalance {
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < MANUAL_WB_HEADER {
+            return Err(EdsError::PropertiesMismatch.into());
+        }
+        let field = |offset: usize| {
+            EdsInt32::from_ne_bytes(bytes[offset..offset + 4].try_into().unwrap_or_default())
+        };
+        let data = &bytes[MANUAL_WB_HEADER..];
+        let data_size = (field(4).max(0) as usize).min(data.len());
+        Ok(Self {
+            valid: field(0) != 0,
+            caption: eds_string(&bytes[8..MANUAL_WB_HEADER]),
+            data: data[..data_size].to_vec(),
+        })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut caption = [0; MANUAL_WB_CAPTION];
+        let len = self.caption.len().min(MANUAL_WB_CAPTION - 1);
+        caption[..len].copy_from_slice(&self.caption.as_bytes()[..len]);
+        let mut bytes = Vec::with_capacity(MANUAL_WB_HEADER + self.data.len());
+        bytes.extend_from_slice(&EdsInt32::from(self.valid).to_ne_bytes());
+        bytes.extend_from_slice(&(self.data.len() as EdsInt32).to_ne_bytes());
+        bytes.extend_from_slice(&caption);
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+}
+
+impl PropertyType for ManualWhiteBalance {
+    fn from_value(value: PropertyValue) -> Result<Self, Error> {
+        Self::decode(&Vec::<u8>::from_value(value)?)
+    }
+
+    fn into_value(self) -> PropertyValue {
+        PropertyValue::ByteBlock(self.encode())
+    }
+}
+
+impl CameraSession {
+    /// Color temperature, in Kelvin, used with [`EdsWhiteBalance::ColorTemp`].
+    pub async fn get_color_temperature(&self) -> Result<EdsUInt32, Error> {
+        self.get::<props::ColorTemperature>().await
+    }
+
+    /// Sets the color temperature, in Kelvin. Fails with
+    /// `EdsError::InvalidParameter` if the camera does not allow it, or, if
+    /// it does not list the allowed values, if it is outside of
+    /// [`COLOR_TEMPERATURE_RANGE`] or not a multiple of [`COLOR_TEMPERATURE_STEP`].
+    pub async fn set_color_temperature(&self, kelvin: EdsUInt32) -> Result<(), Error> {
+        self.check_color_temperature::<props::ColorTemperature>(kelvin)
+            .await?;
+        self.set::<props::ColorTemperature>(kelvin).await
+    }
+
+    /// Color temperature of the live view, in Kelvin.
+    pub async fn get_evf_color_temperature(&self) -> Result<EdsUInt32, Error> {
+        self.get::<props::EvfColorTemperature>().await
+    }
+
+    /// Sets the color temperature of the live view, validated as by
+    /// [`CameraSession::set_color_temperature`].
+    pub async fn set_evf_color_temperature(&self, kelvin: EdsUInt32) -> Result<(), Error> {
+        self.check_color_temperature::<props::EvfColorTemperature>(kelvin)
+            .await?;
+        self.set::<props::EvfColorTemperature>(kelvin).await
+    }
+
+    async fn check_color_temperature<P>(&self, kelvin: EdsUInt32) -> Result<(), Error>
+    where
+        P: Property<Value = EdsUInt32>,
+    {
+        let allowed = self.describe::<P>().await?.values;
+        let valid = if allowed.is_empty() {
+            COLOR_TEMPERATURE_RANGE.contains(&kelvin)
+                && kelvin.is_multiple_of(COLOR_TEMPERATURE_STEP)
+        } else {
+            allowed.contains(&kelvin)
+        };
+        if !valid {
+            return Err(EdsError::InvalidParameter.into());
+        }
+        Ok(())
+    }
+
+    pub async fn get_white_balance_shift(&self) -> Result<WbShift, Error> {
+        self.get::<props::WhiteBalanceShift>().await
+    }
+
+    /// Sets the white balance shift. Fails with `EdsError::InvalidParameter` if
+    /// an axis is outside of [`WB_SHIFT_RANGE`].
+    pub async fn set_white_balance_shift(&self, shift: WbShift) -> Result<(), Error> {
+        if !(WB_SHIFT_RANGE.contains(&shift.ab) && WB_SHIFT_RANGE.contains(&shift.gm)) {
+            return Err(EdsError::InvalidParameter.into());
+        }
+        self.set::<props::WhiteBalanceShift>(shift).await
+    }
+
+    pub async fn get_manual_white_balance(&self) -> Result<ManualWhiteBalance, Error> {
+        self.get::<props::ManualWhiteBalanceData>().await
+    }
+
+    /// Writes manual white balance data, e.g. read from another camera. The
+    /// caption must be shorter than 32 bytes.
+    pub async fn set_manual_white_balance(&self, value: ManualWhiteBalance) -> Result<(), Error> {
+        check_eds_string(&value.caption, MANUAL_WB_CAPTION)?;
+        self.set::<props::ManualWhiteBalanceData>(value).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_white_balance_round_trips() {
+        let value = ManualWhiteBalance {
+            valid: true,
+            caption: "Studio".to_string(),
+            data: vec![1, 2, 3, 4, 5],
+        };
+        let bytes = value.encode();
+        assert_eq!(bytes.len(), MANUAL_WB_HEADER + 5);
+        assert_eq!(ManualWhiteBalance::decode(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn longest_caption_round_trips() {
+        let value = ManualWhiteBalance {
+            valid: false,
+            caption: "c".repeat(MANUAL_WB_CAPTION - 1),
+            data: vec![],
+        };
+        assert_eq!(ManualWhiteBalance::decode(&value.encode()).unwrap(), value);
+    }
+
+    #[test]
+    fn short_buffer_is_rejected() {
+        let bytes = vec![0; MANUAL_WB_HEADER - 1];
+        assert_eq!(
+            ManualWhiteBalance::decode(&bytes).unwrap_err().code(),
+            EdsError::PropertiesMismatch
+        );
+    }
+
+    #[test]
+    fn data_size_is_clamped_to_the_buffer() {
+        let mut bytes = ManualWhiteBalance {
+            valid: true,
+            caption: String::new(),
+            data: vec![7; 3],
+        }
+        .encode();
+        bytes[4..8].copy_from_slice(&100i32.to_ne_bytes());
+        assert_eq!(ManualWhiteBalance::decode(&bytes).unwrap().data, [7; 3]);
+    }
+}
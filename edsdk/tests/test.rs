@@ -4,53 +4,45 @@ use std::{
     io::Write,
     sync::{
         Arc,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicU32, Ordering},
     },
     time::Duration,
 };
-use tokio::{sync::Mutex, time};
+use tokio::{
+    sync::{Mutex, broadcast},
+    time,
+};
 
 async fn _download_evf_aux(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    out_stream_image_ref: EdsRefWrapper<EdsEvfImageRef>,
-    out_stream: EdsRefWrapper<EdsStreamRef>,
-) -> Result<Vec<u8>, EdsError> {
+    camera_ref: &CameraSession,
+    out_stream_image_ref: &EvfImage,
+    out_stream: &Stream,
+) -> Result<Vec<u8>, Error> {
     eds_download_evf_image(camera_ref, out_stream_image_ref).await?;
     let data = data_from_out_stream(out_stream).await?;
     Ok(data)
 }
 
-async fn _download_evf(
-    camera_ref: EdsRefWrapper<EdsBaseRef>,
-    nb_frame: u64,
-) -> Result<(), EdsError> {
+async fn _download_evf(camera_ref: &CameraSession, nb_frame: u64) -> Result<(), Error> {
     let out_stream = eds_create_memory_stream(0)?;
-    let out_stream_image_ref = unsafe { eds_create_evf_image_ref(out_stream.clone()).await? };
+    let out_stream_image_ref = eds_create_evf_image_ref(&out_stream).await?;
 
     for _ in 0..nb_frame {
-        match _download_evf_aux(
-            camera_ref.clone(),
-            out_stream_image_ref.clone(),
-            out_stream.clone(),
-        )
-        .await
-        {
+        match _download_evf_aux(camera_ref, &out_stream_image_ref, &out_stream).await {
             Ok(data) => {
                 println!("{}", data.len())
             }
             Err(err) => eprintln!("{err:?}"),
         }
     }
-
-    eds_release(out_stream_image_ref).await?;
-    eds_release(out_stream).await
+    Ok(())
 }
 
 // fn progress_handler(
 //     percent: EdsUInt32,
 //     _context: Arc<Mutex<ProgressContext>>,
 //     _cancel: *mut EdsBool,
-// ) -> Result<(), EdsError> {
+// ) -> Result<(), Error> {
 //     println!("{}", percent);
 //     if percent == 100 {
 //         println!("Download is done !")
@@ -58,15 +50,14 @@ async fn _download_evf(
 //     Ok(())
 // }
 
-async fn download(in_ref: EdsRefWrapper<EdsBaseRef>) -> Result<Vec<u8>, EdsError> {
+async fn download(in_ref: &DirectoryItem) -> Result<Vec<u8>, Error> {
     println!("Start Download");
-    let dir_info = eds_get_directory_item_info(in_ref.clone()).await?;
+    let dir_info = eds_get_directory_item_info(in_ref).await?;
     let out_stream = eds_create_memory_stream(dir_info.size)?;
     // let _progress_context = set_progress_callback!(out_stream, progress_handler);
-    eds_download(in_ref.clone(), dir_info.size, out_stream.clone()).await?;
-    eds_download_complete(in_ref.clone()).await?;
-    let data = data_from_out_stream(out_stream.clone()).await?;
-    eds_release(out_stream).await?;
+    eds_download(in_ref, dir_info.size, &out_stream).await?;
+    eds_download_complete(in_ref).await?;
+    let data = data_from_out_stream(&out_stream).await?;
 
     let path = format!("../images/{}", dir_info.get_sz_file_name());
     let mut file = File::create(path).unwrap();
@@ -77,128 +68,101 @@ async fn download(in_ref: EdsRefWrapper<EdsBaseRef>) -> Result<Vec<u8>, EdsError
     Ok(data)
 }
 
-async fn get_event(term: Arc<AtomicBool>) {
-    while term.load(Ordering::SeqCst) {
-        if let Err(err) = eds_get_event() {
-            eprintln!("{err:?}")
-        }
-
-        time::sleep(Duration::from_millis(50)).await;
-    }
-}
-
-fn obj_handler(
-    in_event: EdsObjectEvent,
-    in_ref: EdsRefWrapper<EdsBaseRef>,
-    _context: Arc<Mutex<ObjectContext>>,
-) -> Result<(), EdsError> {
-    tokio::spawn(async move {
-        println!("{in_event:?}");
-        let res = match in_event {
-            EdsObjectEvent::DirItemRequestTransfer => match download(in_ref.clone()).await {
-                Ok(_data) => Ok(()),
-                Err(err) => {
-                    let res = eds_download_cancel(in_ref.clone()).await;
+async fn handle_events(mut events: broadcast::Receiver<CameraEvent>) {
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        println!("{event:?}");
+        if let CameraEvent::Object {
+            event: EdsObjectEvent::DirItemRequestTransfer,
+            object: Some(EventObject::DirectoryItem(item)),
+        } = event
+        {
+            tokio::spawn(async move {
+                if download(&item).await.is_err() {
+                    let res = eds_download_cancel(&item).await;
                     eprintln!("{res:?}");
-                    Err(err)
                 }
-            },
-            _ => Ok(()),
-        };
-
-        match eds_release(in_ref).await {
-            Ok(()) => res,
-            Err(err) => Err(err),
+            });
         }
-    });
-    Ok(())
-}
-
-fn state_handler(
-    event: EdsStateEvent,
-    event_data: EdsUInt32,
-    context: Arc<Mutex<StateContext>>,
-) -> Result<(), EdsError> {
-    println!("{event:?} : {event_data}");
-    match event {
-        EdsStateEvent::JobStatusChanged => {
-            tokio::spawn(async move { context.lock().await.job_status = event_data });
-        }
-        _ => (),
     }
-    Ok(())
-}
-
-fn property_handler(
-    event: EdsPropertyEvent,
-    property_id: EdsPropertyID,
-    event_data: EdsUInt32,
-    _context: Arc<Mutex<PropertyContext>>,
-) -> Result<(), EdsError> {
-    println!("{event:?} : {property_id:?} : {event_data}");
-    Ok(())
 }
 
 #[tokio::test]
-async fn test() -> Result<(), EdsError> {
-    init(|| open_cam(core)).await
+async fn test() -> Result<(), Error> {
+    init(|| async {
+        open_cam(core).await?;
+        open_cam(intervalometer).await
+    })
+    .await
 }
 
-async fn init<T, Fut>(f: T) -> Result<(), EdsError>
+async fn init<T, Fut>(f: T) -> Result<(), Error>
 where
     T: Fn() -> Fut,
-    Fut: Future<Output = Result<(), EdsError>>,
+    Fut: Future<Output = Result<(), Error>>,
 {
     println!("== Initialize sdk ==");
-    eds_initialize_sdk()?;
+    let sdk = Sdk::with_event_pump(EventPumpConfig::default())?;
     match f().await {
         Ok(()) => (),
         Err(err) => eprintln!("{:?}", err),
     }
     println!("== Terminate sdk ==");
-    eds_terminate_sdk()
+    drop(sdk);
+    Ok(())
 }
-fn camera_added_handler(_context: Arc<Mutex<CameraAddedContext>>) -> Result<(), EdsError> {
+fn camera_added_handler(_context: Arc<Mutex<CameraAddedContext>>) -> Result<(), Error> {
     println!("Camera added");
     Ok(())
 }
 
-async fn open_cam<T, Fut>(f: T) -> Result<(), EdsError>
+async fn open_cam<T, Fut>(f: T) -> Result<(), Error>
 where
-    T: Fn(EdsRefWrapper<EdsBaseRef>) -> Fut,
-    Fut: Future<Output = Result<(), EdsError>>,
+    T: Fn(CameraSession) -> Fut,
+    Fut: Future<Output = Result<(), Error>>,
 {
     let camera_added_context = Arc::new(Mutex::new(CameraAddedContext {}));
     set_camera_added!(camera_added_context, camera_added_handler);
 
-    let camera_list_ref = eds_get_camera_list()?;
-    let num_of_camera = eds_get_child_count(camera_list_ref.clone()).await?;
-    assert!(num_of_camera > 0, "No camera found");
-    let camera_ref = eds_get_child_at_index(camera_list_ref.clone(), 0).await?;
-    eds_release(camera_list_ref.clone()).await?;
+    let camera_ref = {
+        let camera_list_ref = eds_get_camera_list()?;
+        let num_of_camera = eds_get_child_count(&camera_list_ref).await?;
+        assert!(num_of_camera > 0, "No camera found");
+        eds_get_child_at_index(&camera_list_ref, 0).await?
+    };
     println!("== Open camera session ==");
-    eds_open_session(camera_ref.clone()).await?;
-    match f(camera_ref.clone()).await {
-        Ok(()) => (),
-        Err(err) => eprintln!("{:?}", err),
+    let session = camera_ref.open_session().await?;
+    if let Err(err) = f(session).await {
+        eprintln!("{:?}", err);
     }
-    println!("== Close camera session ==");
-    eds_close_session(camera_ref.clone()).await?;
-    eds_release(camera_ref).await
+    Ok(())
 }
 
-async fn wait_job(state_contexe: Arc<Mutex<StateContext>>) {
-    while state_contexe.lock().await.job_status > 0 {
+async fn wait_job(job_status: &AtomicU32) {
+    while job_status.load(Ordering::SeqCst) > 0 {
         time::sleep(Duration::from_secs(1)).await;
     }
 }
 
-async fn core(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<(), EdsError> {
-    let _object_context = set_object_event_handler!(camera_ref, obj_handler);
-    let state_context = set_state_event_handler!(camera_ref, state_handler);
-    let _property_context = set_property_event_handler!(camera_ref, property_handler);
+async fn core(session: CameraSession) -> Result<(), Error> {
+    let camera_ref = &session;
+    tokio::spawn(handle_events(camera_ref.events()));
+    let job_status = Arc::new(AtomicU32::new(0));
+    let _state_handler = camera_ref.set_state_handler({
+        let job_status = job_status.clone();
+        move |event, data| {
+            println!("{event:?} : {data}");
+            if let EdsStateEvent::JobStatusChanged = event {
+                job_status.store(data, Ordering::SeqCst);
+            }
+        }
+    });
 
-    set_save_to(camera_ref.clone(), EdsSaveTo::Host).await?;
+    camera_ref.set_save_to(EdsSaveTo::Host).await?;
 
     let in_capacity = TagEdsCapacity {
         number_of_free_clusters: 0x7FFFFFFF,
@@ -206,45 +170,76 @@ async fn core(camera_ref: EdsRefWrapper<EdsBaseRef>) -> Result<(), EdsError> {
         reset: true,
     };
 
-    eds_set_capacity(camera_ref.clone(), in_capacity).await?;
+    camera_ref.set_capacity(in_capacity).await?;
 
-    set_evf_depth_of_field_preview(camera_ref.clone(), EdsEvfDepthOfFieldPreview::Off).await?;
-    let term = Arc::new(AtomicBool::new(true));
-    tokio::spawn(get_event(term.clone()));
+    camera_ref
+        .set_evf_depth_of_field_preview(EdsEvfDepthOfFieldPreview::Off)
+        .await?;
 
-    let av = get_av(camera_ref.clone()).await?;
+    let av = camera_ref.get_av().await?;
     println!("{av:?}");
 
-    let tv = get_tv(camera_ref.clone()).await?;
+    let tv = camera_ref.get_tv().await?;
     println!("{tv:?}");
 
+    let mut tv = camera_ref.watch::<props::Tv>().await?;
+    tokio::spawn(async move {
+        while tv.changed().await.is_ok() {
+            println!("Tv changed to {}", *tv.borrow());
+        }
+    });
+
     time::sleep(Duration::from_secs(1)).await;
 
     println!("== Mode photo ==");
-    set_mode(camera_ref.clone(), Mode::Photo).await?;
+    camera_ref.set_mode(Mode::Photo).await?;
 
     for _ in 0..2 {
         println!("== Shoot ==");
-        eds_send_command(camera_ref.clone(), PressShutterButton, Completely).await?;
-        eds_send_command(camera_ref.clone(), PressShutterButton, Off).await?;
-        wait_job(state_context.clone()).await;
+        camera_ref
+            .send_command(PressShutterButton, Completely)
+            .await?;
+        camera_ref.send_command(PressShutterButton, Off).await?;
+        wait_job(&job_status).await;
         time::sleep(Duration::from_secs(2)).await;
     }
 
     // highgui::destroy_all_windows().unwrap_or_default();
 
-    // set_mode(camera_ref, Mode::Video)?;
+    // camera_ref.set_mode(Mode::Video).await?;
     // time::sleep(Duration::from_secs(1)).await;
 
     // download_evf(camera_ref, 1000)?;
 
-    println!("== Set term false ==");
-    term.store(false, Ordering::SeqCst);
-
     println!("== Wait status job is zero ==");
-    wait_job(state_context.clone()).await;
+    wait_job(&job_status).await;
 
     time::sleep(Duration::from_secs(2)).await;
 
+    println!("== Close camera session ==");
+    session.close().await
+}
+
+async fn intervalometer(session: CameraSession) -> Result<(), Error> {
+    // The run is spawned on a task holding on to the session.
+    let session = Arc::new(session);
+    session.set_save_to(EdsSaveTo::Host).await?;
+
+    println!("== Intervalometer ==");
+    let config = IntervalometerConfig::new(Duration::from_secs(5), IntervalEnd::Count(3));
+    let mut frames = session.run_intervalometer(config)?;
+    while let Some(frame) = frames.recv().await {
+        println!(
+            "Frame {} {:?}, drift {:?}, mean drift {:?}",
+            frame.index,
+            frame.outcome,
+            frame.drift,
+            frame.stats.mean_drift()
+        );
+    }
+
+    println!("== Close camera session ==");
+    // The last reference closes the session, the run releasing its own on exit.
+    drop(session);
     Ok(())
 }